@@ -0,0 +1,58 @@
+use std::io::{self, Read};
+use std::sync::{Arc, Mutex};
+
+/// Reader adapter that fails once its input turns out shorter or longer than
+/// the declared `Content-Length`, so a file changing during an upload aborts
+/// the request instead of sending a malformed body.
+pub(crate) struct LengthCheckedReader<R> {
+    inner: R,
+    declared: u64,
+    read: u64,
+    mismatch: Arc<Mutex<Option<u64>>>,
+}
+
+/// Reports the byte count a `LengthCheckedReader` saw when it failed.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct LengthMismatch(Arc<Mutex<Option<u64>>>);
+
+impl LengthMismatch {
+    /// Bytes read when the mismatch was detected, if it was.
+    pub(crate) fn actual(&self) -> Option<u64> {
+        *self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<R> LengthCheckedReader<R> {
+    pub(crate) fn new(inner: R, declared: u64) -> (Self, LengthMismatch) {
+        let mismatch = LengthMismatch::default();
+        let reader = Self {
+            inner,
+            declared,
+            read: 0,
+            mismatch: mismatch.0.clone(),
+        };
+        (reader, mismatch)
+    }
+}
+
+impl<R: Read> Read for LengthCheckedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        let total = self.read + count as u64;
+        let short = count == 0 && !buf.is_empty() && total < self.declared;
+        if short || total > self.declared {
+            *self
+                .mismatch
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(total);
+            return Err(io::Error::other(
+                "upload body does not match Content-Length",
+            ));
+        }
+        self.read = total;
+        Ok(count)
+    }
+}
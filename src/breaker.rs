@@ -0,0 +1,95 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+/// Settings for `ClientBuilder::circuit_breaker`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failed attempts that open the circuit.
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a trial request is let through.
+    pub open_duration: Duration,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    consecutive_failures: u32,
+    opened_at: Option<SystemTime>,
+    /// A half-open trial request is in flight.
+    probing: bool,
+}
+
+/// Circuit breaker over API attempts, shared by clones of a client.
+///
+/// Closed, it counts consecutive failures. Once they reach the threshold it
+/// opens and rejects attempts until `open_duration` has passed; then one
+/// trial attempt is let through (half-open), which closes the circuit on
+/// success and reopens it on failure.
+#[derive(Clone, Debug)]
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Arc<Mutex<State>>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config: CircuitBreakerConfig {
+                failure_threshold: config.failure_threshold.max(1),
+                ..config
+            },
+            state: Arc::default(),
+        }
+    }
+
+    /// Whether an attempt may be sent at `now`. While half-open only the
+    /// first caller gets through.
+    pub(crate) fn allow(&self, now: SystemTime) -> bool {
+        let mut state = self.lock();
+        let Some(opened_at) = state.opened_at else {
+            return true;
+        };
+        let elapsed = now.duration_since(opened_at).unwrap_or_default();
+        if elapsed < self.config.open_duration || state.probing {
+            return false;
+        }
+        state.probing = true;
+        true
+    }
+
+    /// Record the outcome of an attempt let through by `allow`.
+    pub(crate) fn record(&self, success: bool, now: SystemTime) {
+        let mut state = self.lock();
+        if success {
+            *state = State::default();
+            return;
+        }
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        if state.probing || state.consecutive_failures >= self.config.failure_threshold {
+            if state.opened_at.is_none() || state.probing {
+                log::warn!(
+                    target: "releasy_client",
+                    "circuit breaker open after {} consecutive failures; rejecting calls for {:?}",
+                    state.consecutive_failures,
+                    self.config.open_duration
+                );
+            }
+            state.opened_at = Some(now);
+            state.probing = false;
+        }
+    }
+
+    /// Note an attempt let through by `allow` whose outcome says nothing
+    /// about the service, such as a malformed request. The circuit stays as
+    /// it was, but a half-open trial slot is freed for the next caller.
+    pub(crate) fn release(&self) {
+        self.lock().probing = false;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::sync::{Arc, Mutex};
+
+/// Default size of the buffers used to stream downloads, the same as
+/// `io::copy` uses.
+pub(crate) const DEFAULT_TRANSFER_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Idle buffers kept for reuse; any beyond this are freed when returned.
+const MAX_IDLE_BUFFERS: usize = 16;
+
+/// Fixed-size transfer buffers shared by a client and its clones, so
+/// response bodies copied out with `BufferPool::copy` reuse allocations
+/// instead of making their own. Uploads are not pooled: ureq reads request
+/// bodies through its own, larger buffer.
+#[derive(Clone)]
+pub(crate) struct BufferPool {
+    inner: Arc<PoolInner>,
+}
+
+struct PoolInner {
+    size: usize,
+    idle: Mutex<Vec<Box<[u8]>>>,
+}
+
+impl fmt::Debug for BufferPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferPool")
+            .field("size", &self.inner.size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BufferPool {
+    /// Pool of `size`-byte buffers; sizes below 1 are treated as 1.
+    pub(crate) fn new(size: usize) -> Self {
+        Self {
+            inner: Arc::new(PoolInner {
+                size: size.max(1),
+                idle: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Take a buffer from the pool, allocating one if none is idle. It goes
+    /// back to the pool when dropped.
+    pub(crate) fn get(&self) -> PooledBuffer {
+        let buffer = self
+            .inner
+            .idle
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .pop()
+            .unwrap_or_else(|| vec![0; self.inner.size].into_boxed_slice());
+        PooledBuffer {
+            buffer: Some(buffer),
+            pool: self.inner.clone(),
+        }
+    }
+
+    /// `io::copy` through a pooled buffer.
+    pub(crate) fn copy<R, W>(&self, reader: &mut R, writer: &mut W) -> io::Result<u64>
+    where
+        R: Read + ?Sized,
+        W: Write + ?Sized,
+    {
+        let mut buffer = self.get();
+        let mut copied = 0;
+        loop {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => return Ok(copied),
+                Ok(read) => read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            writer.write_all(&buffer[..read])?;
+            copied += read as u64;
+        }
+    }
+}
+
+/// A buffer checked out of a `BufferPool`.
+pub(crate) struct PooledBuffer {
+    buffer: Option<Box<[u8]>>,
+    pool: Arc<PoolInner>,
+}
+
+impl std::ops::Deref for PooledBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.buffer.as_deref().unwrap_or_default()
+    }
+}
+
+impl std::ops::DerefMut for PooledBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.buffer.as_deref_mut().unwrap_or_default()
+    }
+}
+
+impl Drop for PooledBuffer {
+    fn drop(&mut self) {
+        if let Some(buffer) = self.buffer.take() {
+            let mut idle = self
+                .pool
+                .idle
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if idle.len() < MAX_IDLE_BUFFERS {
+                idle.push(buffer);
+            }
+        }
+    }
+}
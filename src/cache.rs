@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use ureq::http::header::{ACCEPT, CACHE_CONTROL};
+use ureq::http::{HeaderMap, Method, Request, Response, StatusCode};
+use ureq::middleware::{Middleware, MiddlewareNext};
+use ureq::{Body, SendBody};
+
+use crate::checksum::to_hex;
+use crate::clock::ClockHandle;
+
+/// Auth headers whose values distinguish cache entries, including the
+/// customer an impersonating client acts for.
+const AUTH_HEADERS: [&str; 4] = [
+    "x-releasy-admin-key",
+    "x-releasy-api-key",
+    "authorization",
+    "x-releasy-on-behalf-of",
+];
+
+/// Settings for `ClientBuilder::response_cache`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseCacheConfig {
+    /// Maximum number of cached responses; the least recently used is evicted.
+    pub capacity: usize,
+    /// How long a cached response is served.
+    pub ttl: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct CacheKey {
+    uri: String,
+    /// Digest of the credentials sent, so clients sharing an agent never see
+    /// each other's responses.
+    credentials: String,
+}
+
+#[derive(Debug)]
+struct Entry {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Vec<u8>,
+    stored_at: SystemTime,
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    map: HashMap<CacheKey, Entry>,
+    uses: u64,
+}
+
+/// LRU cache of successful JSON GET responses, installed as `ureq` middleware
+/// so it is shared by every clone of the client.
+///
+/// Only requests asking for JSON are cached, which keeps artifact downloads
+/// out. Responses with `Cache-Control: no-store` are not stored, and any
+/// other method invalidates cached responses for the same path.
+#[derive(Clone, Debug)]
+pub(crate) struct ResponseCache {
+    config: ResponseCacheConfig,
+    clock: ClockHandle,
+    entries: Arc<Mutex<Entries>>,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(config: ResponseCacheConfig, clock: ClockHandle) -> Self {
+        Self {
+            config,
+            clock,
+            entries: Arc::default(),
+        }
+    }
+
+    fn lookup(&self, key: &CacheKey) -> Option<Response<Body>> {
+        let now = self.clock.now();
+        let mut entries = self.lock();
+        entries.uses += 1;
+        let uses = entries.uses;
+        let fresh = entries.map.get(key).is_some_and(|entry| {
+            now.duration_since(entry.stored_at)
+                .is_ok_and(|age| age < self.config.ttl)
+        });
+        if !fresh {
+            entries.map.remove(key);
+            return None;
+        }
+        let entry = entries.map.get_mut(key)?;
+        entry.last_used = uses;
+        Some(rebuild(entry.status, &entry.headers, entry.body.clone()))
+    }
+
+    fn store(&self, key: CacheKey, status: StatusCode, headers: HeaderMap, body: Vec<u8>) {
+        if self.config.capacity == 0 {
+            return;
+        }
+        let stored_at = self.clock.now();
+        let mut entries = self.lock();
+        entries.uses += 1;
+        let last_used = entries.uses;
+        if !entries.map.contains_key(&key) && entries.map.len() >= self.config.capacity {
+            let oldest = entries
+                .map
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.map.remove(&oldest);
+            }
+        }
+        entries.map.insert(
+            key,
+            Entry {
+                status,
+                headers,
+                body,
+                stored_at,
+                last_used,
+            },
+        );
+    }
+
+    fn invalidate_path(&self, path: &str) {
+        self.lock().map.retain(|key, _| uri_path(&key.uri) != path);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Middleware for ResponseCache {
+    fn handle(
+        &self,
+        request: Request<SendBody>,
+        next: MiddlewareNext,
+    ) -> Result<Response<Body>, ureq::Error> {
+        if request.method() != Method::GET {
+            self.invalidate_path(request.uri().path());
+            return next.handle(request);
+        }
+        let wants_json = request
+            .headers()
+            .get(ACCEPT)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.contains("application/json"));
+        if !wants_json {
+            return next.handle(request);
+        }
+        let key = cache_key(&request);
+        if let Some(response) = self.lookup(&key) {
+            return Ok(response);
+        }
+        let response = next.handle(request)?;
+        let no_store = response
+            .headers()
+            .get_all(CACHE_CONTROL)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|directive| directive.trim().eq_ignore_ascii_case("no-store"));
+        if response.status() != StatusCode::OK || no_store {
+            return Ok(response);
+        }
+        let (parts, mut body) = response.into_parts();
+        let bytes = body.read_to_vec()?;
+        self.store(key, parts.status, parts.headers.clone(), bytes.clone());
+        Ok(rebuild(parts.status, &parts.headers, bytes))
+    }
+}
+
+fn cache_key(request: &Request<SendBody>) -> CacheKey {
+    let mut hasher = Sha256::new();
+    for name in AUTH_HEADERS {
+        if let Some(value) = request.headers().get(name) {
+            hasher.update(name.as_bytes());
+            hasher.update(b":");
+            hasher.update(value.as_bytes());
+            hasher.update(b"\n");
+        }
+    }
+    CacheKey {
+        uri: request.uri().to_string(),
+        credentials: to_hex(&hasher.finalize()),
+    }
+}
+
+fn rebuild(status: StatusCode, headers: &HeaderMap, body: Vec<u8>) -> Response<Body> {
+    let mut response = Response::new(Body::builder().data(body));
+    *response.status_mut() = status;
+    *response.headers_mut() = headers.clone();
+    response
+}
+
+fn uri_path(uri: &str) -> &str {
+    let path = uri
+        .split_once("://")
+        .map(|(_, rest)| rest.find('/').map(|index| &rest[index..]).unwrap_or("/"))
+        .unwrap_or(uri);
+    path.split('?').next().unwrap_or(path)
+}
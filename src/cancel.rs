@@ -0,0 +1,49 @@
+use std::io::{self, Read};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Shared flag used to abort an in-flight transfer.
+///
+/// Clone the token, hand one copy to a `*_with_cancel` method, and call
+/// `cancel()` on another (for example from a UI thread). The transfer stops at
+/// the next chunk boundary and the method returns `Error::Cancelled`.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of every transfer observing this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Reader adapter that fails with an I/O error once its token is cancelled.
+pub(crate) struct CancellableReader<R> {
+    inner: R,
+    token: CancellationToken,
+}
+
+impl<R> CancellableReader<R> {
+    pub(crate) fn new(inner: R, token: CancellationToken) -> Self {
+        Self { inner, token }
+    }
+}
+
+impl<R: Read> Read for CancellableReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.token.is_cancelled() {
+            return Err(io::Error::other("transfer cancelled"));
+        }
+        self.inner.read(buf)
+    }
+}
@@ -0,0 +1,197 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use md5::Md5;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Hash algorithm of a `Checksum`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumAlgorithm {
+    Sha256,
+    Md5,
+}
+
+impl ChecksumAlgorithm {
+    /// Return the name used as the checksum prefix, e.g. `sha256`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Sha256 => "sha256",
+            ChecksumAlgorithm::Md5 => "md5",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [ChecksumAlgorithm::Sha256, ChecksumAlgorithm::Md5]
+            .into_iter()
+            .find(|algorithm| algorithm.as_str().eq_ignore_ascii_case(name))
+    }
+
+    /// Length of the algorithm's digest in hex characters.
+    fn hex_len(&self) -> usize {
+        match self {
+            ChecksumAlgorithm::Sha256 => 64,
+            ChecksumAlgorithm::Md5 => 32,
+        }
+    }
+}
+
+impl fmt::Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How a checksum is written for the server: `sha256:<hex>` or bare hex.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumFormat {
+    #[default]
+    Prefixed,
+    Bare,
+}
+
+/// A digest together with the algorithm that produced it.
+///
+/// Displays in the prefixed form (`sha256:<hex>`); use `format` for servers
+/// that expect bare hex.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Checksum {
+    pub algorithm: ChecksumAlgorithm,
+    /// Lowercase hex digest.
+    pub value: String,
+}
+
+impl Checksum {
+    /// Hash `bytes` with `algorithm`.
+    pub fn of_bytes(algorithm: ChecksumAlgorithm, bytes: &[u8]) -> Self {
+        let value = match algorithm {
+            ChecksumAlgorithm::Sha256 => to_hex(&Sha256::digest(bytes)),
+            ChecksumAlgorithm::Md5 => to_hex(&Md5::digest(bytes)),
+        };
+        Self { algorithm, value }
+    }
+
+    /// Hash the file at `path` with `algorithm`, streaming it from disk.
+    pub fn of_file(algorithm: ChecksumAlgorithm, path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let (value, _) = match algorithm {
+            ChecksumAlgorithm::Sha256 => digest_file::<Sha256>(path)?,
+            ChecksumAlgorithm::Md5 => digest_file::<Md5>(path)?,
+        };
+        Ok(Self { algorithm, value })
+    }
+
+    /// Parse a checksum as registered with the server: `sha256:<hex>`,
+    /// `md5:<hex>`, or bare hex whose length identifies the algorithm.
+    /// Returns `None` when no supported algorithm matches.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (algorithm, hex) = match value.split_once(':') {
+            Some((name, hex)) => (ChecksumAlgorithm::from_name(name)?, hex),
+            None => (
+                [ChecksumAlgorithm::Sha256, ChecksumAlgorithm::Md5]
+                    .into_iter()
+                    .find(|algorithm| algorithm.hex_len() == value.len())?,
+                value,
+            ),
+        };
+        (hex.len() == algorithm.hex_len() && hex.bytes().all(|byte| byte.is_ascii_hexdigit())).then(
+            || Self {
+                algorithm,
+                value: hex.to_ascii_lowercase(),
+            },
+        )
+    }
+
+    /// Render the checksum as `format` asks.
+    pub fn format(&self, format: ChecksumFormat) -> String {
+        match format {
+            ChecksumFormat::Prefixed => self.to_string(),
+            ChecksumFormat::Bare => self.value.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.value)
+    }
+}
+
+/// Compute the lowercase hex SHA-256 digest and size of a file.
+pub(crate) fn sha256_file(path: &Path) -> io::Result<(String, u64)> {
+    digest_file::<Sha256>(path)
+}
+
+fn digest_file<D: Digest>(path: &Path) -> io::Result<(String, u64)> {
+    let mut file = File::open(path)?;
+    let mut hasher = D::new();
+    let mut buffer = [0u8; 64 * 1024];
+    let mut size = 0u64;
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        size += read as u64;
+    }
+    Ok((to_hex(&hasher.finalize()), size))
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Writer adapter that hashes everything passed through to the inner writer.
+pub(crate) struct HashingWriter<'a, W: Write> {
+    inner: &'a mut W,
+    hasher: Hasher,
+}
+
+enum Hasher {
+    Sha256(Sha256),
+    Md5(Md5),
+}
+
+impl<'a, W: Write> HashingWriter<'a, W> {
+    pub(crate) fn new(inner: &'a mut W, algorithm: ChecksumAlgorithm) -> Self {
+        let hasher = match algorithm {
+            ChecksumAlgorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            ChecksumAlgorithm::Md5 => Hasher::Md5(Md5::new()),
+        };
+        Self { inner, hasher }
+    }
+
+    pub(crate) fn finish(self) -> Checksum {
+        match self.hasher {
+            Hasher::Sha256(hasher) => Checksum {
+                algorithm: ChecksumAlgorithm::Sha256,
+                value: to_hex(&hasher.finalize()),
+            },
+            Hasher::Md5(hasher) => Checksum {
+                algorithm: ChecksumAlgorithm::Md5,
+                value: to_hex(&hasher.finalize()),
+            },
+        }
+    }
+}
+
+impl<W: Write> Write for HashingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        match &mut self.hasher {
+            Hasher::Sha256(hasher) => hasher.update(&buf[..written]),
+            Hasher::Md5(hasher) => hasher.update(&buf[..written]),
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
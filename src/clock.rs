@@ -0,0 +1,99 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Source of the current time and of delays, installed via
+/// `ClientBuilder::clock`.
+///
+/// The client reads the time through its clock for retry budgets and expiry
+/// checks, and waits through it for retry backoff, so tests can substitute
+/// a `ManualClock`.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+
+    /// Block for `duration`; a test clock may advance its time instead.
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// The real wall clock. This is the default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that only moves when told to. `sleep` advances it without
+/// blocking. Clones share the same time.
+#[derive(Clone, Debug)]
+pub struct ManualClock {
+    now: Arc<Mutex<SystemTime>>,
+}
+
+impl ManualClock {
+    pub fn new(start: SystemTime) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(start)),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.lock();
+        *now += duration;
+    }
+
+    pub fn set(&self, time: SystemTime) {
+        *self.lock() = time;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SystemTime> {
+        self.now
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        *self.lock()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
+
+/// Shared clock handle held by clients and builders.
+#[derive(Clone)]
+pub(crate) struct ClockHandle(Arc<dyn Clock>);
+
+impl ClockHandle {
+    pub(crate) fn new(clock: impl Clock + 'static) -> Self {
+        Self(Arc::new(clock))
+    }
+
+    pub(crate) fn now(&self) -> SystemTime {
+        self.0.now()
+    }
+
+    pub(crate) fn sleep(&self, duration: Duration) {
+        self.0.sleep(duration);
+    }
+}
+
+impl Default for ClockHandle {
+    fn default() -> Self {
+        Self::new(SystemClock)
+    }
+}
+
+impl fmt::Debug for ClockHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Clock(..)")
+    }
+}
@@ -0,0 +1,143 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::breaker::CircuitBreakerConfig;
+use crate::cache::ResponseCacheConfig;
+use crate::client::Auth;
+use crate::retry::{RetryBudget, RetryPolicy};
+
+/// Plain configuration for a `Client`, for callers that merge settings from
+/// several sources before building.
+///
+/// Every field mirrors a `ClientBuilder` option. Pass the finished value to
+/// `Client::from_config`. A custom `ureq::Agent` cannot be expressed here; use
+/// `ClientBuilder::from_config(..)?.agent(..)` for that.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClientConfig {
+    pub base_url: String,
+    /// Serialized as its kind only, e.g. `{"kind": "api"}`, so secrets never
+    /// end up in dumped configs. Deserializing needs the `secret` back.
+    #[serde(serialize_with = "serialize_auth_kind")]
+    pub auth: Auth,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Sent as `X-Releasy-Api-Version`, see `ClientBuilder::api_version`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_global: Option<Duration>,
+    /// Ceiling on a call's total time including retries, see
+    /// `ClientBuilder::overall_deadline`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overall_deadline: Option<Duration>,
+    /// Static `(host, addr)` DNS overrides, see `ClientBuilder::resolve`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resolve_overrides: Vec<(String, SocketAddr)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_redirects: Option<u32>,
+    /// Proxy URL, see `ClientBuilder::proxy`. `None` uses the proxy
+    /// environment variables, if any. Credentials in the URL are masked
+    /// when serializing.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_proxy"
+    )]
+    pub proxy: Option<String>,
+    /// See `ClientBuilder::tcp_nodelay`; `None` keeps the default (on).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tcp_nodelay: Option<bool>,
+    /// See `ClientBuilder::pool_idle_timeout`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_idle_timeout: Option<Duration>,
+    #[serde(default)]
+    pub capture_request_body_on_error: bool,
+    #[serde(default)]
+    pub strict_deserialization: bool,
+    #[serde(default)]
+    pub log_bodies: bool,
+    /// See `ClientBuilder::compress_requests`.
+    #[serde(default)]
+    pub compress_requests: bool,
+    /// Largest `limit` sent on list calls, see `ClientBuilder::max_page_size`.
+    /// `None` uses the default of 100.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_page_size: Option<u32>,
+    /// See `ClientBuilder::transfer_buffer_size`. `None` uses 8 KiB.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transfer_buffer_size: Option<usize>,
+    /// See `ClientBuilder::max_upload_bytes`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_upload_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_budget: Option<RetryBudget>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Hosts server-provided URLs may point to, see
+    /// `ClientBuilder::allowed_download_hosts`. `None` allows every host.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_download_hosts: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_cache: Option<ResponseCacheConfig>,
+}
+
+impl ClientConfig {
+    /// Configuration with the given base URL and auth and every option off.
+    pub fn new(base_url: impl Into<String>, auth: Auth) -> Self {
+        Self {
+            base_url: base_url.into(),
+            auth,
+            user_agent: None,
+            api_version: None,
+            timeout_global: None,
+            overall_deadline: None,
+            resolve_overrides: Vec::new(),
+            max_redirects: None,
+            proxy: None,
+            tcp_nodelay: None,
+            pool_idle_timeout: None,
+            capture_request_body_on_error: false,
+            strict_deserialization: false,
+            log_bodies: false,
+            compress_requests: false,
+            max_page_size: None,
+            transfer_buffer_size: None,
+            max_upload_bytes: None,
+            retry: None,
+            retry_budget: None,
+            circuit_breaker: None,
+            allowed_download_hosts: None,
+            response_cache: None,
+        }
+    }
+}
+
+fn serialize_auth_kind<S: Serializer>(auth: &Auth, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(1))?;
+    map.serialize_entry("kind", auth.kind())?;
+    map.end()
+}
+
+/// Write the proxy URL with its password masked, so a dumped config does not
+/// leak it; the masked URL no longer parses, so reusing it fails loudly.
+fn serialize_proxy<S: Serializer>(
+    proxy: &Option<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let masked = proxy.as_deref().map(|proxy| {
+        let start = proxy.find("://").map_or(0, |index| index + 3);
+        match proxy[start..].split_once('@') {
+            Some((userinfo, host)) => {
+                let user = userinfo.split_once(':').map_or(userinfo, |(user, _)| user);
+                format!("{}{}:[redacted]@{}", &proxy[..start], user, host)
+            }
+            None => proxy.to_string(),
+        }
+    });
+    masked.serialize(serializer)
+}
@@ -0,0 +1,139 @@
+use std::collections::BTreeSet;
+
+/// Paths this client calls, in OpenAPI template form. Optional paths are
+/// only served by some deployments and are not reported when missing.
+const KNOWN_PATHS: &[(&str, bool)] = &[
+    ("/health", false),
+    ("/live", false),
+    ("/openapi.json", false),
+    ("/ready", false),
+    ("/v1/admin/audit-events", false),
+    ("/v1/admin/customers", false),
+    ("/v1/admin/customers/{customer_id}", false),
+    ("/v1/admin/customers/{customer_id}/entitlements", false),
+    ("/v1/admin/customers/{customer_id}/entitlements/bulk", true),
+    (
+        "/v1/admin/customers/{customer_id}/entitlements/{entitlement_id}",
+        false,
+    ),
+    ("/v1/admin/entitlements", true),
+    ("/v1/admin/keys", false),
+    ("/v1/admin/keys/revoke", false),
+    ("/v1/admin/keys/revoke-batch", true),
+    ("/v1/admin/users", false),
+    ("/v1/admin/users/{id}", false),
+    ("/v1/admin/users/{id}/groups", false),
+    ("/v1/admin/users/{id}/metadata", true),
+    ("/v1/admin/users/{id}/reset-credentials", false),
+    ("/v1/auth/introspect", false),
+    ("/v1/downloads/token", false),
+    ("/v1/downloads/{token}", false),
+    ("/v1/products", true),
+    ("/v1/releases", false),
+    ("/v1/releases/events", true),
+    ("/v1/releases/{release_id}", false),
+    ("/v1/releases/{release_id}/artifacts", false),
+    ("/v1/releases/{release_id}/artifacts/presign", false),
+    ("/v1/releases/{release_id}/artifacts/upload", true),
+    ("/v1/releases/{release_id}/audit-events", true),
+    ("/v1/releases/{release_id}/publish", false),
+    ("/v1/releases/{release_id}/unpublish", false),
+];
+
+/// Differences between the paths this client uses and those a server
+/// documents, produced by `Client::verify_contract`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContractReport {
+    /// Paths the client calls that the server does not document.
+    pub missing: Vec<String>,
+    /// Paths the server documents that the client does not call.
+    pub extra: Vec<String>,
+}
+
+impl ContractReport {
+    /// Whether every path the client depends on is served.
+    pub fn is_compatible(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Compare the `paths` of an OpenAPI document with `KNOWN_PATHS`.
+    ///
+    /// Parameter names are ignored, so `/users/{id}` matches `/users/{user_id}`.
+    pub(crate) fn from_openapi(document: &serde_json::Value) -> Self {
+        let served: BTreeSet<(String, &str)> = document
+            .get("paths")
+            .and_then(|paths| paths.as_object())
+            .map(|paths| {
+                paths
+                    .keys()
+                    .map(|path| (path_shape(path), path.as_str()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let served_shapes: BTreeSet<&str> =
+            served.iter().map(|(shape, _)| shape.as_str()).collect();
+        let known_shapes: BTreeSet<String> = KNOWN_PATHS
+            .iter()
+            .map(|(path, _)| path_shape(path))
+            .collect();
+        let missing = KNOWN_PATHS
+            .iter()
+            .filter(|(path, optional)| {
+                !optional && !served_shapes.contains(path_shape(path).as_str())
+            })
+            .map(|(path, _)| path.to_string())
+            .collect();
+        let extra = served
+            .iter()
+            .filter(|(shape, _)| !known_shapes.contains(shape))
+            .map(|(_, path)| path.to_string())
+            .collect();
+        Self { missing, extra }
+    }
+}
+
+/// Map a concrete request path to the `KNOWN_PATHS` template it was built
+/// from, e.g. `/v1/releases/rel-1` to `/v1/releases/{release_id}`.
+///
+/// Literal segments win over parameters, so `/v1/downloads/token` is not
+/// reported as `/v1/downloads/{token}`. Unknown paths map to `"other"`.
+pub(crate) fn path_template(path: &str) -> &'static str {
+    let segments: Vec<&str> = path.split('/').collect();
+    KNOWN_PATHS
+        .iter()
+        .filter_map(|(template, _)| {
+            let parts: Vec<&str> = template.split('/').collect();
+            if parts.len() != segments.len() {
+                return None;
+            }
+            let mut literals = 0;
+            for (part, segment) in parts.iter().zip(&segments) {
+                if part.starts_with('{') && part.ends_with('}') {
+                    if segment.is_empty() {
+                        return None;
+                    }
+                } else if part == segment {
+                    literals += 1;
+                } else {
+                    return None;
+                }
+            }
+            Some((literals, *template))
+        })
+        .max_by_key(|(literals, _)| *literals)
+        .map_or("other", |(_, template)| template)
+}
+
+/// Replace every `{param}` segment with `{}`.
+fn path_shape(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            if segment.starts_with('{') && segment.ends_with('}') {
+                "{}"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
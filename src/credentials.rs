@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::client::Auth;
+use crate::error::{Error, Result};
+
+/// Profile used when `Client::from_profile` is not given one.
+pub(crate) const DEFAULT_PROFILE: &str = "default";
+
+/// `~/.releasy/credentials.toml`, or `None` when no home directory is set.
+pub(crate) fn default_path() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .filter(|home| !home.is_empty())
+        .map(|home| {
+            PathBuf::from(home)
+                .join(".releasy")
+                .join("credentials.toml")
+        })
+}
+
+/// Read `profile` from the credentials file at `path`, returning its base
+/// URL and credentials.
+pub(crate) fn load_profile(path: &Path, profile: &str) -> Result<(String, Auth)> {
+    let error = |message: String| Error::Credentials {
+        path: path.to_path_buf(),
+        message,
+    };
+    let text = std::fs::read_to_string(path).map_err(|err| error(err.to_string()))?;
+    let mut profiles = parse(&text).map_err(error)?;
+    let mut fields = profiles
+        .remove(profile)
+        .ok_or_else(|| error(format!("profile `{}` not found", profile)))?;
+    let mut take = |key: &str| {
+        fields
+            .remove(key)
+            .ok_or_else(|| error(format!("profile `{}` has no `{}`", profile, key)))
+    };
+    let base_url = take("base_url")?;
+    let auth = match take("auth")?.as_str() {
+        "none" => Auth::None,
+        "api" => Auth::ApiKey(take("key")?),
+        "admin" => Auth::AdminKey(take("key")?),
+        "operator" => Auth::OperatorJwt(take("key")?),
+        "admin_operator" => Auth::AdminWithOperator {
+            admin_key: take("admin_key")?,
+            operator_jwt: take("operator_jwt")?,
+        },
+        other => {
+            return Err(error(format!(
+                "profile `{}` has unknown auth `{}` (expected none, api, admin, operator, or admin_operator)",
+                profile, other
+            )));
+        }
+    };
+    Ok((base_url, auth))
+}
+
+/// Parse the subset of TOML used by credentials files: `[profile]` tables
+/// holding `key = "string"` pairs, with `#` comments. Basic (`"..."`) and
+/// literal (`'...'`) strings are supported.
+fn parse(text: &str) -> std::result::Result<HashMap<String, HashMap<String, String>>, String> {
+    let mut profiles: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut current: Option<String> = None;
+    for (index, line) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix('[') {
+            let (name, trailing) = rest
+                .split_once(']')
+                .ok_or_else(|| format!("line {}: unterminated table header", line_no))?;
+            check_trailing(trailing, line_no)?;
+            let name = name.trim().trim_matches('"').to_string();
+            profiles.entry(name.clone()).or_default();
+            current = Some(name);
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("line {}: expected `key = \"value\"`", line_no))?;
+        let key = key.trim();
+        let Some(profile) = &current else {
+            return Err(format!(
+                "line {}: `{}` is outside any profile",
+                line_no, key
+            ));
+        };
+        let (value, trailing) = parse_string(value.trim())
+            .ok_or_else(|| format!("line {}: `{}` must be a quoted string", line_no, key))?;
+        check_trailing(trailing, line_no)?;
+        let fields = profiles.entry(profile.clone()).or_default();
+        if fields.insert(key.to_string(), value).is_some() {
+            return Err(format!("line {}: duplicate key `{}`", line_no, key));
+        }
+    }
+    Ok(profiles)
+}
+
+fn check_trailing(trailing: &str, line_no: usize) -> std::result::Result<(), String> {
+    let trailing = trailing.trim();
+    if trailing.is_empty() || trailing.starts_with('#') {
+        Ok(())
+    } else {
+        Err(format!("line {}: unexpected `{}`", line_no, trailing))
+    }
+}
+
+/// Parse a string at the start of `input`, returning it and the rest of the
+/// line. Escapes follow TOML: `\uXXXX` and `\UXXXXXXXX` need exactly four
+/// or eight hex digits.
+fn parse_string(input: &str) -> Option<(String, &str)> {
+    if let Some(rest) = input.strip_prefix('\'') {
+        let (value, trailing) = rest.split_once('\'')?;
+        return Some((value.to_string(), trailing));
+    }
+    let rest = input.strip_prefix('"')?;
+    let mut value = String::new();
+    let mut chars = rest.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &rest[index + 1..])),
+            '\\' => match chars.next()?.1 {
+                '"' => value.push('"'),
+                '\\' => value.push('\\'),
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                'b' => value.push('\u{8}'),
+                'f' => value.push('\u{c}'),
+                'u' => value.push(unicode_escape(&mut chars, 4)?),
+                'U' => value.push(unicode_escape(&mut chars, 8)?),
+                _ => return None,
+            },
+            c if c.is_control() && c != '\t' => return None,
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// Read the `digits` hex digits of a `\u` or `\U` escape as a scalar value.
+fn unicode_escape(chars: &mut std::str::CharIndices<'_>, digits: usize) -> Option<char> {
+    let mut code = 0u32;
+    for _ in 0..digits {
+        code = code * 16 + chars.next()?.1.to_digit(16)?;
+    }
+    char::from_u32(code)
+}
@@ -1,41 +1,250 @@
 use std::fmt;
+use std::path::PathBuf;
 
-use crate::models::{EnterpriseErrorBody, ErrorBody};
+use crate::models::{EnterpriseErrorBody, ErrorBody, FieldError};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Errors returned by the client. `Error` is `Send + Sync + 'static`, so it
+/// converts into `Box<dyn std::error::Error + Send + Sync>` with `?`.
 #[derive(Debug)]
 pub enum Error {
     Api {
         status: u16,
         error: Option<ErrorBody>,
         body: Option<String>,
+        /// Serialized request payload, captured when the client is built with
+        /// `capture_request_body_on_error(true)`. Secret fields are redacted.
+        request_body: Option<String>,
+    },
+    /// The request could not be completed; `method` and `url` identify the call.
+    Transport {
+        method: String,
+        url: String,
+        source: ureq::Error,
+    },
+    /// The host name of `url` could not be resolved. Lookup I/O errors are
+    /// only recognized as such with the built-in agent; a custom agent
+    /// reports them as `Transport`.
+    Dns {
+        method: String,
+        url: String,
+        host: String,
+        source: Box<ureq::Error>,
+    },
+    /// The request exceeded a configured timeout; `method` and `url`
+    /// identify the call.
+    Timeout {
+        method: String,
+        url: String,
+        timeout: ureq::Timeout,
+        source: Box<ureq::Error>,
     },
-    Transport(ureq::Error),
     InvalidBaseUrl(String),
+    /// A request failed client-side validation and was not sent.
+    Validation {
+        message: String,
+    },
+    /// A credentials file could not be read, or lacks the requested profile
+    /// or one of its fields. See `Client::from_profile`.
+    Credentials {
+        path: PathBuf,
+        message: String,
+    },
+    /// Builder options that cannot be applied together.
+    ConfigConflict {
+        message: String,
+    },
     MissingLocationHeader,
+    /// A call that returns a resource was answered with 204 No Content, e.g.
+    /// because the server considered the update a no-op.
+    UnexpectedNoContent {
+        method: String,
+        url: String,
+    },
+    /// An API call answered with a redirect that was not followed, see
+    /// `ClientBuilder::max_redirects`.
+    UnexpectedRedirect {
+        status: u16,
+        location: Option<String>,
+    },
+    /// `Client::check_auth` found the API key inactive (revoked or expired).
+    Unauthorized {
+        api_key_id: String,
+    },
+    /// `Client::resolve_download_token_for` was given a token minted for
+    /// another purpose (API error code `purpose_mismatch`).
+    PurposeMismatch {
+        expected: String,
+        message: String,
+    },
+    /// A release could not be found.
+    ReleaseNotFound {
+        release_id: String,
+    },
+    /// A release exists but has no artifact with the given id.
+    ArtifactNotFound {
+        release_id: String,
+        artifact_id: String,
+    },
+    /// No published release of `product` has an artifact for `platform`.
+    NoMatchingArtifact {
+        product: String,
+        platform: String,
+    },
+    /// Downloaded bytes did not match the checksum registered for the artifact.
+    ChecksumMismatch {
+        expected: String,
+        actual: String,
+    },
+    /// An artifact's registered checksum names no supported algorithm, so
+    /// its download cannot be verified.
+    UnsupportedChecksum {
+        checksum: String,
+    },
+    /// A response contained fields unknown to the models (strict mode only).
+    Decode {
+        unknown_fields: Vec<String>,
+    },
+    /// An upload body ended up shorter or longer than its declared
+    /// `Content-Length`, e.g. because the file changed while being read.
+    /// `actual` is the byte count when the difference was detected.
+    SizeMismatch {
+        declared: u64,
+        actual: u64,
+    },
+    /// A presigned upload was refused before sending because it exceeds
+    /// `ClientBuilder::max_upload_bytes`.
+    PayloadTooLarge {
+        size: u64,
+        limit: u64,
+    },
+    /// The circuit breaker is open after repeated failures, so the call was
+    /// not sent. See `ClientBuilder::circuit_breaker`.
+    CircuitOpen,
+    /// A transfer was aborted through its `CancellationToken`.
+    Cancelled,
+    /// A server-provided URL pointed to a host outside the configured allowlist.
+    DisallowedHost {
+        host: String,
+    },
+    /// A ranged download received the full body instead of the requested range.
+    RangeNotSupported,
+    /// `Client::provision_customer` failed after creating the customer.
+    /// `source` is the failed step; `cleanup_errors` lists rollback steps
+    /// that failed as well (empty when the rollback succeeded).
+    ProvisionFailed {
+        customer_id: String,
+        source: Box<Error>,
+        cleanup_errors: Vec<Error>,
+    },
+    /// A chained download failed; `stage` names the step that failed.
+    Download {
+        stage: DownloadStage,
+        source: Box<Error>,
+    },
+}
+
+/// Step of a chained artifact download.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DownloadStage {
+    /// Creating the download token.
+    Token,
+    /// Resolving the token to a redirect location.
+    Resolve,
+    /// Fetching the artifact bytes from the redirect location.
+    Fetch,
+}
+
+impl fmt::Display for DownloadStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DownloadStage::Token => write!(f, "token"),
+            DownloadStage::Resolve => write!(f, "resolve"),
+            DownloadStage::Fetch => write!(f, "fetch"),
+        }
+    }
 }
 
 impl Error {
+    pub(crate) fn transport(method: &str, url: &str, source: ureq::Error) -> Self {
+        match source {
+            ureq::Error::Timeout(timeout) => Error::Timeout {
+                method: method.to_string(),
+                url: url.to_string(),
+                timeout,
+                source: Box::new(source),
+            },
+            ureq::Error::HostNotFound => Error::Dns {
+                method: method.to_string(),
+                url: url.to_string(),
+                host: url
+                    .parse::<ureq::http::Uri>()
+                    .ok()
+                    .and_then(|uri| uri.host().map(str::to_string))
+                    .unwrap_or_default(),
+                source: Box::new(source),
+            },
+            source => Error::Transport {
+                method: method.to_string(),
+                url: url.to_string(),
+                source,
+            },
+        }
+    }
+
+    pub(crate) fn download(stage: DownloadStage, source: Error) -> Self {
+        match source {
+            Error::Cancelled => Error::Cancelled,
+            Error::RangeNotSupported => Error::RangeNotSupported,
+            source => Error::Download {
+                stage,
+                source: Box::new(source),
+            },
+        }
+    }
+
+    /// Return the failed stage for chained download errors.
+    pub fn download_stage(&self) -> Option<DownloadStage> {
+        match self {
+            Error::Download { stage, .. } => Some(*stage),
+            _ => None,
+        }
+    }
+
     /// Return the HTTP status code for API errors.
     pub fn status(&self) -> Option<u16> {
-        match self {
+        match self.root() {
             Error::Api { status, .. } => Some(*status),
             _ => None,
         }
     }
 
+    /// Whether an upload was too large: the server answered 413 Payload Too
+    /// Large, or the size exceeded `max_upload_bytes` before sending. Callers
+    /// can fall back to `Client::upload_artifact_multipart`.
+    pub fn is_payload_too_large(&self) -> bool {
+        matches!(self.root(), Error::PayloadTooLarge { .. }) || self.status() == Some(413)
+    }
+
     /// Return the parsed `ErrorBody` for API errors, when available.
     pub fn api_error(&self) -> Option<&ErrorBody> {
-        match self {
+        match self.root() {
             Error::Api { error, .. } => error.as_ref(),
             _ => None,
         }
     }
 
+    /// Return per-field validation errors for API errors, or an empty slice.
+    pub fn field_errors(&self) -> &[FieldError] {
+        self.api_error()
+            .map(|body| body.error.details.as_slice())
+            .unwrap_or_default()
+    }
+
     /// Parse an `EnterpriseErrorBody` from the raw error body, if present.
     pub fn enterprise_error(&self) -> Option<EnterpriseErrorBody> {
-        match self {
+        match self.root() {
             Error::Api { body, .. } => body
                 .as_ref()
                 .and_then(|body| serde_json::from_str::<EnterpriseErrorBody>(body).ok()),
@@ -45,11 +254,27 @@ impl Error {
 
     /// Return the raw response body for API errors, when available.
     pub fn body(&self) -> Option<&str> {
-        match self {
+        match self.root() {
             Error::Api { body, .. } => body.as_deref(),
             _ => None,
         }
     }
+
+    /// Return the captured (redacted) request body for API errors, when available.
+    pub fn request_body(&self) -> Option<&str> {
+        match self.root() {
+            Error::Api { request_body, .. } => request_body.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Unwrap context-carrying variants down to the underlying error.
+    fn root(&self) -> &Error {
+        match self {
+            Error::Download { source, .. } | Error::ProvisionFailed { source, .. } => source.root(),
+            other => other,
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -65,19 +290,146 @@ impl fmt::Display for Error {
                 status, error.error.code, error.error.message
             ),
             Error::Api { status, .. } => write!(f, "api error (status {})", status),
-            Error::Transport(err) => write!(f, "transport error: {}", err),
+            Error::Transport {
+                method,
+                url,
+                source,
+            } => write!(f, "transport error on {} {}: {}", method, url, source),
+            Error::Dns {
+                method, url, host, ..
+            } => {
+                write!(f, "could not resolve host {} for {} {}", host, method, url)
+            }
+            Error::Timeout {
+                method,
+                url,
+                timeout,
+                ..
+            } => write!(f, "{} {} timed out ({} timeout)", method, url, timeout),
             Error::InvalidBaseUrl(url) => write!(f, "invalid base url: {}", url),
+            Error::Validation { message } => write!(f, "invalid request: {}", message),
+            Error::Credentials { path, message } => {
+                write!(f, "credentials file {}: {}", path.display(), message)
+            }
+            Error::ConfigConflict { message } => {
+                write!(f, "conflicting client configuration: {}", message)
+            }
             Error::MissingLocationHeader => {
                 write!(f, "missing Location header in redirect response")
             }
+            Error::UnexpectedNoContent { method, url } => {
+                write!(
+                    f,
+                    "expected a response body from {} {}, got 204 No Content",
+                    method, url
+                )
+            }
+            Error::UnexpectedRedirect {
+                status,
+                location: Some(location),
+            } => write!(f, "unexpected redirect (status {}) to {}", status, location),
+            Error::UnexpectedRedirect { status, .. } => {
+                write!(
+                    f,
+                    "unexpected redirect (status {}) without Location",
+                    status
+                )
+            }
+            Error::Unauthorized { api_key_id } => {
+                write!(f, "api key {} is not active", api_key_id)
+            }
+            Error::PurposeMismatch { expected, message } => {
+                write!(
+                    f,
+                    "download token is not valid for purpose {}: {}",
+                    expected, message
+                )
+            }
+            Error::ReleaseNotFound { release_id } => {
+                write!(f, "release {} not found", release_id)
+            }
+            Error::ArtifactNotFound {
+                release_id,
+                artifact_id,
+            } => write!(
+                f,
+                "artifact {} not found in release {}",
+                artifact_id, release_id
+            ),
+            Error::NoMatchingArtifact { product, platform } => write!(
+                f,
+                "no published release of {} has an artifact for {}",
+                product, platform
+            ),
+            Error::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "checksum mismatch: expected {}, got {}",
+                expected, actual
+            ),
+            Error::UnsupportedChecksum { checksum } => write!(
+                f,
+                "cannot verify checksum `{}`: unsupported algorithm",
+                checksum
+            ),
+            Error::Decode { unknown_fields } => write!(
+                f,
+                "response contained unknown fields: {}",
+                unknown_fields.join(", ")
+            ),
+            Error::CircuitOpen => write!(f, "circuit breaker open; call not sent"),
+            Error::PayloadTooLarge { size, limit } => write!(
+                f,
+                "upload of {} bytes exceeds the {} byte limit",
+                size, limit
+            ),
+            Error::SizeMismatch { declared, actual } => write!(
+                f,
+                "upload body size changed: declared {} bytes, read {}",
+                declared, actual
+            ),
+            Error::Cancelled => write!(f, "transfer cancelled"),
+            Error::DisallowedHost { host } => {
+                write!(f, "refusing to contact host {} (not allowed)", host)
+            }
+            Error::RangeNotSupported => {
+                write!(f, "server ignored the requested byte range")
+            }
+            Error::ProvisionFailed {
+                customer_id,
+                source,
+                cleanup_errors,
+            } if cleanup_errors.is_empty() => write!(
+                f,
+                "provisioning customer {} failed and was rolled back: {}",
+                customer_id, source
+            ),
+            Error::ProvisionFailed {
+                customer_id,
+                source,
+                cleanup_errors,
+            } => write!(
+                f,
+                "provisioning customer {} failed: {} ({} rollback steps failed)",
+                customer_id,
+                source,
+                cleanup_errors.len()
+            ),
+            Error::Download { stage, source } => {
+                write!(f, "download failed at {} stage: {}", stage, source)
+            }
         }
     }
 }
 
-impl std::error::Error for Error {}
-
-impl From<ureq::Error> for Error {
-    fn from(err: ureq::Error) -> Self {
-        Error::Transport(err)
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport { source, .. } => Some(source),
+            Error::Dns { source, .. } | Error::Timeout { source, .. } => Some(source.as_ref()),
+            Error::Download { source, .. } | Error::ProvisionFailed { source, .. } => {
+                Some(source.as_ref())
+            }
+            _ => None,
+        }
     }
 }
@@ -0,0 +1,31 @@
+//! Response body decoding. With the `simd-json` feature, bodies are parsed by
+//! `simd-json` into the same serde models; otherwise by `serde_json`.
+
+use serde::de::DeserializeOwned;
+use ureq::Body;
+
+#[cfg(not(feature = "simd-json"))]
+pub(crate) fn read_json<T: DeserializeOwned>(body: &mut Body) -> Result<T, ureq::Error> {
+    body.read_json()
+}
+
+#[cfg(feature = "simd-json")]
+pub(crate) fn read_json<T: DeserializeOwned>(body: &mut Body) -> Result<T, ureq::Error> {
+    let bytes = body.read_to_vec()?;
+    from_bytes(bytes).map_err(ureq::Error::Json)
+}
+
+/// Decode an already-read body.
+pub(crate) fn from_str<T: DeserializeOwned>(text: &str) -> serde_json::Result<T> {
+    #[cfg(feature = "simd-json")]
+    return from_bytes(text.as_bytes().to_vec());
+    #[cfg(not(feature = "simd-json"))]
+    return serde_json::from_str(text);
+}
+
+/// `simd-json` parses in place, so it gets its own copy of the bytes.
+#[cfg(feature = "simd-json")]
+fn from_bytes<T: DeserializeOwned>(mut bytes: Vec<u8>) -> serde_json::Result<T> {
+    simd_json::serde::from_slice(&mut bytes)
+        .map_err(<serde_json::Error as serde::de::Error>::custom)
+}
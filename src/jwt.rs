@@ -0,0 +1,69 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Unverified claims read from a JWT payload.
+///
+/// These are decoded without checking the signature, so they are only useful
+/// for local pre-checks (expiry, audience, scopes) before calling the API.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JwtClaims {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    /// Audiences; a single-string `aud` claim becomes a one-element list.
+    #[serde(
+        default,
+        deserialize_with = "string_or_list",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub aud: Vec<String>,
+    /// Space-separated OAuth `scope` claim.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// List-valued `scopes` claim used by some issuers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
+}
+
+impl JwtClaims {
+    /// Return true if `scope` appears in either the `scope` or `scopes` claim.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        let in_scope = self
+            .scope
+            .as_deref()
+            .is_some_and(|value| value.split_whitespace().any(|item| item == scope));
+        let in_scopes = self
+            .scopes
+            .as_ref()
+            .is_some_and(|values| values.iter().any(|item| item == scope));
+        in_scope || in_scopes
+    }
+}
+
+/// Decode the payload segment of a compact JWT, returning `None` if it is malformed.
+pub(crate) fn decode_claims(token: &str) -> Option<JwtClaims> {
+    let mut segments = token.trim().split('.');
+    let (_header, payload, _signature) = (segments.next()?, segments.next()?, segments.next()?);
+    if segments.next().is_some() {
+        return None;
+    }
+    let bytes = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+fn string_or_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
+}
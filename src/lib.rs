@@ -1,9 +1,55 @@
 #![doc = include_str!("../README.md")]
 
+mod body;
+mod breaker;
+mod buffer;
+mod cache;
+mod cancel;
+mod checksum;
 mod client;
+mod clock;
+mod config;
+mod contract;
+mod credentials;
 mod error;
+mod id;
+mod json;
+mod jwt;
+mod metrics;
 mod models;
+mod page;
+mod prepare;
+mod provision;
+mod redact;
+mod retry;
+mod scope;
+mod signing;
+mod sse;
+mod stats;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+mod timestamp;
+mod transport;
 
-pub use crate::client::{Auth, Client, ClientBuilder, DownloadResolution};
-pub use crate::error::{Error, Result};
+pub use crate::breaker::CircuitBreakerConfig;
+pub use crate::cache::ResponseCacheConfig;
+pub use crate::cancel::CancellationToken;
+pub use crate::checksum::{Checksum, ChecksumAlgorithm, ChecksumFormat};
+pub use crate::client::{
+    Auth, Client, ClientBuilder, Created, DownloadMetadata, DownloadResolution, IdempotentResponse,
+    RangeDownload, RangeFallback,
+};
+pub use crate::clock::{Clock, ManualClock, SystemClock};
+pub use crate::config::ClientConfig;
+pub use crate::contract::ContractReport;
+pub use crate::error::{DownloadStage, Error, Result};
+pub use crate::id::{ArtifactId, CustomerId, ReleaseId, UserId};
+pub use crate::jwt::JwtClaims;
 pub use crate::models::*;
+pub use crate::page::{Page, PageToken};
+pub use crate::prepare::{Operation, PreparedRequest};
+pub use crate::provision::{CustomerProvisionResult, CustomerProvisionSpec};
+pub use crate::retry::{Jitter, JitterRng, RetryBudget, RetryPolicy};
+pub use crate::scope::CustomerScope;
+pub use crate::signing::SignableRequest;
+pub use crate::stats::{ClientStats, LatencyStats};
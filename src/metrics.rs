@@ -0,0 +1,39 @@
+//! Request metrics emitted through the `metrics` crate when the `metrics`
+//! feature is enabled; without it every function here is a no-op.
+
+use std::time::Duration;
+
+#[cfg(feature = "metrics")]
+pub(crate) fn record_request(
+    method: &str,
+    path: &'static str,
+    status: Option<u16>,
+    elapsed: Duration,
+) {
+    let status = status.map_or_else(|| "error".to_string(), |status| status.to_string());
+    let labels = [
+        ("method", method.to_string()),
+        ("path", path.to_string()),
+        ("status", status),
+    ];
+    metrics::counter!("releasy_requests_total", &labels).increment(1);
+    metrics::histogram!("releasy_request_duration_seconds", &labels).record(elapsed.as_secs_f64());
+}
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) fn record_request(
+    _method: &str,
+    _path: &'static str,
+    _status: Option<u16>,
+    _elapsed: Duration,
+) {
+}
+
+#[cfg(feature = "metrics")]
+pub(crate) fn record_retry(method: &str, path: &'static str) {
+    let labels = [("method", method.to_string()), ("path", path.to_string())];
+    metrics::counter!("releasy_retries_total", &labels).increment(1);
+}
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) fn record_retry(_method: &str, _path: &'static str) {}
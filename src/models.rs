@@ -1,6 +1,16 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::checksum::{Checksum, ChecksumFormat};
+use crate::error::Error;
+use crate::id::{ArtifactId, CustomerId, ReleaseId, UserId};
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AdminCreateCustomerRequest {
     pub name: String,
@@ -10,8 +20,9 @@ pub struct AdminCreateCustomerRequest {
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AdminCreateCustomerResponse {
-    pub id: String,
+    pub id: CustomerId,
     pub name: String,
+    #[serde(deserialize_with = "crate::timestamp::deserialize")]
     pub created_at: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub plan: Option<String>,
@@ -20,33 +31,68 @@ pub struct AdminCreateCustomerResponse {
 #[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct AdminCustomerListQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub plan: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub limit: Option<i32>,
+    pub limit: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub offset: Option<i32>,
+    pub offset: Option<u32>,
+    /// Extra `(name, value)` parameters sent after the typed ones, for filters
+    /// this crate does not model yet. Values are URL-encoded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_query: Vec<(String, String)>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct AdminCustomerListResponse {
     pub customers: Vec<AdminCustomerResponse>,
-    pub limit: i64,
-    pub offset: i64,
+    pub limit: u32,
+    pub offset: u32,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AdminCustomerResponse {
-    pub id: String,
+    pub id: CustomerId,
     pub name: String,
+    #[serde(deserialize_with = "crate::timestamp::deserialize")]
     pub created_at: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub plan: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::timestamp::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub suspended_at: Option<i64>,
+    /// When the plan last changed, if the deployment reports it.
+    #[serde(
+        default,
+        deserialize_with = "crate::timestamp::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub plan_changed_at: Option<i64>,
+    /// Fields the models do not know about, such as plan-change side effects
+    /// (e.g. a prorated billing preview) returned by some deployments.
+    /// Because they land here, `strict_deserialization` does not reject them.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+/// Formats as `name (id)`, followed by `[plan]` and `[suspended]` when set.
+impl fmt::Display for AdminCustomerResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.id)?;
+        if let Some(plan) = &self.plan {
+            write!(f, " [{}]", plan)?;
+        }
+        if self.suspended_at.is_some() {
+            write!(f, " [suspended]")?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -62,7 +108,7 @@ pub struct AdminUpdateCustomerRequest {
 #[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct UserListQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -74,9 +120,13 @@ pub struct UserListQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_to: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub limit: Option<i32>,
+    pub limit: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
+    /// Extra `(name, value)` parameters sent after the typed ones, for filters
+    /// this crate does not model yet. Values are URL-encoded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_query: Vec<(String, String)>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -88,28 +138,49 @@ pub struct UserListResponse {
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct UserResponse {
-    pub id: String,
+    pub id: UserId,
     pub keycloak_user_id: String,
-    pub customer_id: String,
+    pub customer_id: CustomerId,
     pub email: String,
     pub status: String,
     pub groups: Vec<String>,
+    #[serde(deserialize_with = "crate::timestamp::deserialize")]
     pub created_at: i64,
+    #[serde(deserialize_with = "crate::timestamp::deserialize")]
     pub updated_at: i64,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::timestamp::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub disabled_at: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::timestamp::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub last_synced_at: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Value>,
 }
 
+/// Formats as `Display Name <email> [status]`, or `email [status]` without a
+/// display name.
+impl fmt::Display for UserResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.display_name {
+            Some(name) => write!(f, "{} <{}> [{}]", name, self.email, self.status),
+            None => write!(f, "{} [{}]", self.email, self.status),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct UserCreateRequest {
     pub email: String,
-    pub customer_id: String,
+    pub customer_id: CustomerId,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -143,9 +214,15 @@ pub struct ResetCredentialsRequest {
     pub send_email: Option<bool>,
 }
 
+/// Body of a credential reset answered with 200 instead of 202.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ResetCredentialsResponse {
+    pub reset_link: String,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AdminCreateKeyRequest {
-    pub customer_id: String,
+    pub customer_id: CustomerId,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_at: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -156,14 +233,140 @@ pub struct AdminCreateKeyRequest {
     pub scopes: Option<Vec<String>>,
 }
 
+impl AdminCreateKeyRequest {
+    /// Start building a key request for `customer_id` with typed scopes.
+    pub fn builder(customer_id: impl Into<CustomerId>) -> AdminCreateKeyRequestBuilder {
+        AdminCreateKeyRequestBuilder {
+            request: AdminCreateKeyRequest {
+                customer_id: customer_id.into(),
+                expires_at: None,
+                key_type: None,
+                name: None,
+                scopes: None,
+            },
+        }
+    }
+}
+
+/// Builder for `AdminCreateKeyRequest`, from `AdminCreateKeyRequest::builder`.
+#[derive(Clone, Debug)]
+pub struct AdminCreateKeyRequestBuilder {
+    request: AdminCreateKeyRequest,
+}
+
+impl AdminCreateKeyRequestBuilder {
+    /// Grant `scope`, keeping any already added.
+    pub fn scope(mut self, scope: Scope) -> Self {
+        self.request
+            .scopes
+            .get_or_insert_with(Vec::new)
+            .push(scope.into());
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.request.name = Some(name.into());
+        self
+    }
+
+    pub fn key_type(mut self, key_type: impl Into<String>) -> Self {
+        self.request.key_type = Some(key_type.into());
+        self
+    }
+
+    /// Expire the key at this Unix timestamp.
+    pub fn expires_at(mut self, expires_at: i64) -> Self {
+        self.request.expires_at = Some(expires_at);
+        self
+    }
+
+    pub fn build(self) -> AdminCreateKeyRequest {
+        self.request
+    }
+}
+
+/// Permission granted to an API key.
+///
+/// Known scopes serialize as their `resource:action` strings
+/// (`releases:read`, `releases:write`, `downloads:token`); any other value
+/// round-trips through `Other`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum Scope {
+    ReleasesRead,
+    ReleasesWrite,
+    /// Create download tokens for artifacts.
+    DownloadsToken,
+    Other(String),
+}
+
+impl Scope {
+    /// Return the wire representation of the scope.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Scope::ReleasesRead => "releases:read",
+            Scope::ReleasesWrite => "releases:write",
+            Scope::DownloadsToken => "downloads:token",
+            Scope::Other(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for Scope {
+    fn from(value: &str) -> Self {
+        match value {
+            "releases:read" => Scope::ReleasesRead,
+            "releases:write" => Scope::ReleasesWrite,
+            "downloads:token" => Scope::DownloadsToken,
+            other => Scope::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for Scope {
+    fn from(value: String) -> Self {
+        match Scope::from(value.as_str()) {
+            Scope::Other(_) => Scope::Other(value),
+            known => known,
+        }
+    }
+}
+
+impl From<Scope> for String {
+    fn from(value: Scope) -> Self {
+        match value {
+            Scope::Other(value) => value,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl FromStr for Scope {
+    type Err = Infallible;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(Scope::from(value))
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AdminCreateKeyResponse {
     pub api_key_id: String,
     pub api_key: String,
-    pub customer_id: String,
+    pub customer_id: CustomerId,
     pub key_type: String,
     pub scopes: Vec<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::timestamp::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub expires_at: Option<i64>,
 }
 
@@ -177,56 +380,209 @@ pub struct AdminRevokeKeyResponse {
     pub api_key_id: String,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AdminRevokeKeysRequest {
+    pub api_key_ids: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AdminRevokeKeysResponse {
+    pub results: Vec<AdminRevokeKeyResult>,
+}
+
+impl AdminRevokeKeysResponse {
+    /// Return true when every key in the batch was revoked.
+    pub fn all_revoked(&self) -> bool {
+        self.results.iter().all(|result| result.revoked)
+    }
+
+    /// Iterate over the keys the server failed to revoke.
+    pub fn failures(&self) -> impl Iterator<Item = &AdminRevokeKeyResult> {
+        self.results.iter().filter(|result| !result.revoked)
+    }
+}
+
+/// Outcome for a single key in a batch revocation.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AdminRevokeKeyResult {
+    pub api_key_id: String,
+    pub revoked: bool,
+    /// Why the key was not revoked, when `revoked` is false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorDetail>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ApiKeyIntrospection {
     pub active: bool,
     pub api_key_id: String,
-    pub customer_id: String,
+    pub customer_id: CustomerId,
     pub key_type: String,
     pub scopes: Vec<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::timestamp::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub expires_at: Option<i64>,
 }
 
+impl ApiKeyIntrospection {
+    /// Whether the key expires within `window` of `now`. Keys without an
+    /// expiry never do.
+    pub fn expires_within(&self, window: Duration, now: SystemTime) -> bool {
+        self.expires_at
+            .map(epoch_seconds_to_system_time)
+            .is_some_and(|expires_at| {
+                now.checked_add(window)
+                    .is_none_or(|deadline| expires_at <= deadline)
+            })
+    }
+}
+
+/// Target platform of a release artifact.
+///
+/// Known platforms serialize as lowercase strings (`linux`, `macos`,
+/// `windows`); any other value round-trips through `Other`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum Platform {
+    Linux,
+    MacOs,
+    Windows,
+    Other(String),
+}
+
+impl Platform {
+    /// Return the platform of the host this code was compiled for.
+    pub fn current() -> Self {
+        Platform::from(std::env::consts::OS)
+    }
+
+    /// Return the wire representation of the platform.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Platform::Linux => "linux",
+            Platform::MacOs => "macos",
+            Platform::Windows => "windows",
+            Platform::Other(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for Platform {
+    fn from(value: &str) -> Self {
+        match value {
+            "linux" => Platform::Linux,
+            "macos" => Platform::MacOs,
+            "windows" => Platform::Windows,
+            other => Platform::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for Platform {
+    fn from(value: String) -> Self {
+        match Platform::from(value.as_str()) {
+            Platform::Other(_) => Platform::Other(value),
+            known => known,
+        }
+    }
+}
+
+impl From<Platform> for String {
+    fn from(value: Platform) -> Self {
+        match value {
+            Platform::Other(value) => value,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl FromStr for Platform {
+    type Err = Infallible;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(Platform::from(value))
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ArtifactPresignRequest {
     pub filename: String,
-    pub platform: String,
+    pub platform: Platform,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ArtifactPresignResponse {
-    pub artifact_id: String,
+    pub artifact_id: ArtifactId,
+    /// Storage key chosen by the server. The naming scheme is not part of the
+    /// API contract and differs between deployments, so it cannot be
+    /// computed ahead of the presign call; log or audit this value instead.
     pub object_key: String,
     pub upload_url: String,
+    #[serde(deserialize_with = "crate::timestamp::deserialize")]
     pub expires_at: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Headers the upload request must carry for the signature to match.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub required_headers: HashMap<String, String>,
+}
+
+impl ArtifactPresignResponse {
+    pub fn expires_at_system_time(&self) -> SystemTime {
+        epoch_seconds_to_system_time(self.expires_at)
+    }
+
+    /// Whether the upload URL has expired at `now` (e.g. `client.now()`).
+    pub fn is_expired_at(&self, now: SystemTime) -> bool {
+        self.expires_at_system_time() <= now
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ArtifactRegisterRequest {
-    pub artifact_id: String,
+    pub artifact_id: ArtifactId,
+    /// The `object_key` from `ArtifactPresignResponse`, passed through as is.
     pub object_key: String,
     pub checksum: String,
     pub size: i64,
-    pub platform: String,
+    pub platform: Platform,
+}
+
+impl ArtifactRegisterRequest {
+    /// Set `checksum` from a computed `Checksum`, written the way the server
+    /// expects it.
+    pub fn with_checksum(mut self, checksum: &Checksum, format: ChecksumFormat) -> Self {
+        self.checksum = checksum.format(format);
+        self
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ArtifactRegisterResponse {
-    pub id: String,
-    pub release_id: String,
+    pub id: ArtifactId,
+    pub release_id: ReleaseId,
     pub object_key: String,
     pub checksum: String,
     pub size: i64,
-    pub platform: String,
+    pub platform: Platform,
+    #[serde(deserialize_with = "crate::timestamp::deserialize")]
     pub created_at: i64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ArtifactSummary {
-    pub id: String,
+    pub id: ArtifactId,
     pub object_key: String,
-    pub platform: String,
+    pub platform: Platform,
     pub checksum: String,
     pub size: i64,
 }
@@ -234,7 +590,7 @@ pub struct ArtifactSummary {
 #[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct AuditEventListQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub actor: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -244,16 +600,20 @@ pub struct AuditEventListQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_to: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub limit: Option<i32>,
+    pub limit: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub offset: Option<i32>,
+    pub offset: Option<u32>,
+    /// Extra `(name, value)` parameters sent after the typed ones, for filters
+    /// this crate does not model yet. Values are URL-encoded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_query: Vec<(String, String)>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct AuditEventListResponse {
     pub events: Vec<AuditEventResponse>,
-    pub limit: i64,
-    pub offset: i64,
+    pub limit: u32,
+    pub offset: u32,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -261,16 +621,75 @@ pub struct AuditEventResponse {
     pub id: String,
     pub actor: String,
     pub event: String,
+    #[serde(deserialize_with = "crate::timestamp::deserialize")]
     pub created_at: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payload: Option<Value>,
 }
 
+/// What a download token was minted for, e.g. a fresh install or an update.
+///
+/// Sent as the `purpose` of `DownloadTokenRequest` (via `String::from`) and
+/// checked by `Client::resolve_download_token_for`. Values other than
+/// `install` and `update` round-trip through `Other`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum DownloadPurpose {
+    Install,
+    Update,
+    Other(String),
+}
+
+impl DownloadPurpose {
+    /// Return the wire representation of the purpose.
+    pub fn as_str(&self) -> &str {
+        match self {
+            DownloadPurpose::Install => "install",
+            DownloadPurpose::Update => "update",
+            DownloadPurpose::Other(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for DownloadPurpose {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for DownloadPurpose {
+    fn from(value: &str) -> Self {
+        match value {
+            "install" => DownloadPurpose::Install,
+            "update" => DownloadPurpose::Update,
+            other => DownloadPurpose::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for DownloadPurpose {
+    fn from(value: String) -> Self {
+        match DownloadPurpose::from(value.as_str()) {
+            DownloadPurpose::Other(_) => DownloadPurpose::Other(value),
+            known => known,
+        }
+    }
+}
+
+impl From<DownloadPurpose> for String {
+    fn from(value: DownloadPurpose) -> Self {
+        match value {
+            DownloadPurpose::Other(value) => value,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct DownloadTokenRequest {
-    pub artifact_id: String,
+    pub artifact_id: ArtifactId,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_in_seconds: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -280,9 +699,36 @@ pub struct DownloadTokenRequest {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct DownloadTokenResponse {
     pub download_url: String,
+    #[serde(deserialize_with = "crate::timestamp::deserialize")]
     pub expires_at: i64,
 }
 
+impl DownloadTokenResponse {
+    pub fn expires_at_system_time(&self) -> SystemTime {
+        epoch_seconds_to_system_time(self.expires_at)
+    }
+
+    /// Whether the token has expired at `now` (e.g. `client.now()`).
+    pub fn is_expired_at(&self, now: SystemTime) -> bool {
+        self.expires_at_system_time() <= now
+    }
+
+    /// Whether the token has expired at `now`, in Unix seconds. A token is
+    /// expired from its `expires_at` second onwards.
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at <= now
+    }
+
+    /// Time left until the token expires at `now`, in Unix seconds, or `None`
+    /// once it has expired.
+    pub fn time_to_expiry(&self, now: i64) -> Option<Duration> {
+        u64::try_from(self.expires_at.saturating_sub(now))
+            .ok()
+            .filter(|seconds| *seconds > 0)
+            .map(Duration::from_secs)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct EntitlementCreateRequest {
     pub product: String,
@@ -293,6 +739,23 @@ pub struct EntitlementCreateRequest {
     pub metadata: Option<Value>,
 }
 
+impl EntitlementCreateRequest {
+    /// Entitlement for `product` starting at `starts_at` with no expiry.
+    pub fn perpetual(product: impl Into<String>, starts_at: i64) -> Self {
+        Self {
+            product: product.into(),
+            starts_at,
+            ends_at: None,
+            metadata: None,
+        }
+    }
+
+    /// Check that `ends_at`, when set, is strictly after `starts_at`.
+    pub fn validate(&self) -> crate::Result<()> {
+        validate_entitlement_window(Some(self.starts_at), self.ends_at)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct EntitlementUpdateRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -305,44 +768,216 @@ pub struct EntitlementUpdateRequest {
     pub metadata: Option<Value>,
 }
 
+impl EntitlementUpdateRequest {
+    /// Check that `ends_at` is strictly after `starts_at` when both are set.
+    pub fn validate(&self) -> crate::Result<()> {
+        validate_entitlement_window(self.starts_at, self.ends_at)
+    }
+}
+
+fn validate_entitlement_window(starts_at: Option<i64>, ends_at: Option<i64>) -> crate::Result<()> {
+    match (starts_at, ends_at) {
+        (Some(starts_at), Some(ends_at)) if ends_at <= starts_at => Err(Error::Validation {
+            message: format!(
+                "entitlement ends_at ({}) must be after starts_at ({})",
+                ends_at, starts_at
+            ),
+        }),
+        _ => Ok(()),
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct EntitlementResponse {
     pub id: String,
-    pub customer_id: String,
+    pub customer_id: CustomerId,
     pub product: String,
+    #[serde(deserialize_with = "crate::timestamp::deserialize")]
     pub starts_at: i64,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::timestamp::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub ends_at: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Value>,
 }
 
+impl EntitlementResponse {
+    pub fn starts_at_system_time(&self) -> SystemTime {
+        epoch_seconds_to_system_time(self.starts_at)
+    }
+
+    /// Return `None` for entitlements without an expiry.
+    pub fn ends_at_system_time(&self) -> Option<SystemTime> {
+        self.ends_at.map(epoch_seconds_to_system_time)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EntitlementBulkCreateRequest {
+    pub entitlements: Vec<EntitlementCreateRequest>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EntitlementBulkCreateResponse {
+    pub results: Vec<EntitlementBulkCreateResult>,
+}
+
+impl EntitlementBulkCreateResponse {
+    /// Return true when every entitlement in the batch was created.
+    pub fn all_created(&self) -> bool {
+        self.results
+            .iter()
+            .all(|result| result.entitlement.is_some())
+    }
+
+    /// Iterate over the entitlements that were created.
+    pub fn created(&self) -> impl Iterator<Item = &EntitlementResponse> {
+        self.results
+            .iter()
+            .filter_map(|result| result.entitlement.as_ref())
+    }
+
+    /// Iterate over the items the server failed to create.
+    pub fn failures(&self) -> impl Iterator<Item = &EntitlementBulkCreateResult> {
+        self.results
+            .iter()
+            .filter(|result| result.entitlement.is_none())
+    }
+}
+
+/// Outcome for a single item in a bulk entitlement creation.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EntitlementBulkCreateResult {
+    /// Position of the item in the request's `entitlements` list.
+    pub index: usize,
+    pub product: String,
+    /// The created entitlement, when the item succeeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entitlement: Option<EntitlementResponse>,
+    /// Why the item failed, when it did.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorDetail>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct EntitlementListQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub product: Option<String>,
+    /// Only return entitlements active at this unix timestamp.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub limit: Option<i32>,
+    pub active_at: Option<i64>,
+    /// Include entitlements whose `ends_at` has passed.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub offset: Option<i32>,
+    pub include_expired: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    /// Extra `(name, value)` parameters sent after the typed ones, for filters
+    /// this crate does not model yet. Values are URL-encoded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_query: Vec<(String, String)>,
+}
+
+/// Filters for `Client::list_all_entitlements`, which lists entitlements
+/// across every customer.
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct GlobalEntitlementListQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_id: Option<CustomerId>,
+    /// Only return entitlements active at this unix timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_at: Option<i64>,
+    /// Include entitlements whose `ends_at` has passed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_expired: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    /// Extra `(name, value)` parameters sent after the typed ones, for filters
+    /// this crate does not model yet. Values are URL-encoded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_query: Vec<(String, String)>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct EntitlementListResponse {
     pub entitlements: Vec<EntitlementResponse>,
-    pub limit: i64,
-    pub offset: i64,
+    pub limit: u32,
+    pub offset: u32,
 }
 
+/// Error envelope of a failed API call.
+///
+/// RFC 7807 `application/problem+json` bodies are normalized into the same
+/// shape: `code` comes from the last segment of `type` (or `title` when the
+/// type is absent or `about:blank`) and `message` from `detail` (or `title`).
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ErrorBody {
     pub error: ErrorDetail,
 }
 
+impl ErrorBody {
+    /// Parse an error response body, trying the native envelope first and
+    /// then problem details. `problem_json` relaxes the shape check for bodies
+    /// labelled `application/problem+json`.
+    pub(crate) fn parse(body: &str, problem_json: bool) -> Option<Self> {
+        if let Ok(parsed) = serde_json::from_str::<ErrorBody>(body) {
+            return Some(parsed);
+        }
+        let problem = serde_json::from_str::<ProblemDetails>(body).ok()?;
+        let has_text = problem.title.is_some() || problem.detail.is_some();
+        if !has_text || !(problem_json || problem.problem_type.is_some()) {
+            return None;
+        }
+        let code = problem
+            .problem_type
+            .as_deref()
+            .filter(|kind| *kind != "about:blank")
+            .and_then(|kind| kind.trim_end_matches('/').rsplit('/').next())
+            .filter(|code| !code.is_empty())
+            .map(str::to_string)
+            .or_else(|| problem.title.clone())
+            .unwrap_or_default();
+        let message = problem.detail.or(problem.title).unwrap_or_default();
+        Some(ErrorBody {
+            error: ErrorDetail {
+                code,
+                message,
+                details: Vec::new(),
+            },
+        })
+    }
+}
+
+/// RFC 7807 problem details, as sent by some gateways.
+#[derive(Deserialize)]
+struct ProblemDetails {
+    #[serde(rename = "type")]
+    problem_type: Option<String>,
+    title: Option<String>,
+    detail: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ErrorDetail {
     pub code: String,
     pub message: String,
+    /// Per-field validation failures, typically sent with 422 responses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<FieldError>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -359,6 +994,16 @@ pub struct EnterpriseErrorDetail {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct HealthResponse {
     pub status: String,
+    /// Per-component health (e.g. `database`, `storage`), when reported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<HashMap<String, ComponentHealth>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ComponentHealth {
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -367,17 +1012,174 @@ pub struct ReleaseCreateRequest {
     pub version: String,
 }
 
+impl ReleaseCreateRequest {
+    /// Check that `product` and `version` are not blank.
+    pub fn validate(&self) -> crate::Result<()> {
+        for (field, value) in [("product", &self.product), ("version", &self.version)] {
+            if value.trim().is_empty() {
+                return Err(Error::Validation {
+                    message: format!("release {} must not be empty", field),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct ReleaseResponse {
-    pub id: String,
+    pub id: ReleaseId,
     pub product: String,
     pub version: String,
     pub status: String,
+    #[serde(deserialize_with = "crate::timestamp::deserialize")]
     pub created_at: i64,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::timestamp::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub published_at: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub artifacts: Option<Vec<ArtifactSummary>>,
+    /// Last modification time, for incremental sync checkpoints.
+    #[serde(
+        default,
+        deserialize_with = "crate::timestamp::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub updated_at: Option<i64>,
+}
+
+/// Formats as `product@version [status]`.
+impl fmt::Display for ReleaseResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{} [{}]", self.product, self.version, self.status)
+    }
+}
+
+impl ReleaseResponse {
+    pub fn created_at_system_time(&self) -> SystemTime {
+        epoch_seconds_to_system_time(self.created_at)
+    }
+
+    /// Return `None` while the release is unpublished.
+    pub fn published_at_system_time(&self) -> Option<SystemTime> {
+        self.published_at.map(epoch_seconds_to_system_time)
+    }
+
+    /// Compare this release's artifacts with `other`'s, matching them by
+    /// platform. Both releases must have been fetched with
+    /// `include_artifacts`; a release without artifacts compares as empty.
+    /// Checksums are compared ignoring case and an algorithm prefix such as
+    /// `sha256:`; digests of different algorithms always count as changed.
+    pub fn artifact_diff(&self, other: &ReleaseResponse) -> ArtifactDiff {
+        let ours = self.artifacts.as_deref().unwrap_or_default();
+        let theirs = other.artifacts.as_deref().unwrap_or_default();
+        let find = |artifacts: &[ArtifactSummary], platform: &Platform| {
+            artifacts
+                .iter()
+                .find(|artifact| artifact.platform == *platform)
+                .cloned()
+        };
+        let mut diff = ArtifactDiff::default();
+        for artifact in ours {
+            match find(theirs, &artifact.platform) {
+                None => diff.only_in_self.push(artifact.clone()),
+                Some(other) if !same_checksum(&artifact.checksum, &other.checksum) => {
+                    diff.changed.push((artifact.clone(), other));
+                }
+                Some(_) => {}
+            }
+        }
+        for artifact in theirs {
+            if find(ours, &artifact.platform).is_none() {
+                diff.only_in_other.push(artifact.clone());
+            }
+        }
+        diff
+    }
+}
+
+/// Result of `ReleaseResponse::artifact_diff`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ArtifactDiff {
+    /// Artifacts for platforms the other release lacks.
+    pub only_in_self: Vec<ArtifactSummary>,
+    /// Artifacts for platforms only the other release has.
+    pub only_in_other: Vec<ArtifactSummary>,
+    /// `(self, other)` artifact pairs for the same platform whose checksums
+    /// differ.
+    pub changed: Vec<(ArtifactSummary, ArtifactSummary)>,
+}
+
+impl ArtifactDiff {
+    /// Whether both releases carry the same artifacts.
+    pub fn is_empty(&self) -> bool {
+        self.only_in_self.is_empty() && self.only_in_other.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn same_checksum(left: &str, right: &str) -> bool {
+    match (Checksum::parse(left), Checksum::parse(right)) {
+        (Some(left), Some(right)) => left == right,
+        // Not a recognizable digest: fall back to comparing the text.
+        _ => {
+            let normalize = |value: &str| {
+                let value = value.trim();
+                value
+                    .strip_prefix("sha256:")
+                    .unwrap_or(value)
+                    .to_ascii_lowercase()
+            };
+            normalize(left) == normalize(right)
+        }
+    }
+}
+
+/// A release with its artifacts, from `Client::list_releases_with_artifacts`.
+///
+/// `release.artifacts` is always `None`; the artifacts live in `artifacts`,
+/// which is empty when the release has none.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReleaseWithArtifacts {
+    pub release: ReleaseResponse,
+    pub artifacts: Vec<ArtifactSummary>,
+}
+
+impl From<ReleaseResponse> for ReleaseWithArtifacts {
+    fn from(mut release: ReleaseResponse) -> Self {
+        let artifacts = release.artifacts.take().unwrap_or_default();
+        Self { release, artifacts }
+    }
+}
+
+/// Convert Unix epoch seconds, as used by every timestamp field, to
+/// `SystemTime`. Values beyond what the platform can represent saturate to
+/// the nearest representable time instead of panicking.
+fn epoch_seconds_to_system_time(seconds: i64) -> SystemTime {
+    let shift = |time: SystemTime, step: Duration| {
+        if seconds >= 0 {
+            time.checked_add(step)
+        } else {
+            time.checked_sub(step)
+        }
+    };
+    let mut remaining = Duration::from_secs(seconds.unsigned_abs());
+    let mut time = UNIX_EPOCH;
+    let mut step = remaining;
+    // Take the largest steps that still fit, halving on overflow.
+    while !step.is_zero() {
+        match shift(time, step) {
+            Some(shifted) => {
+                time = shifted;
+                remaining -= step;
+                step = step.min(remaining);
+            }
+            None => step /= 2,
+        }
+    }
+    time
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
@@ -386,19 +1188,109 @@ pub struct ReleaseListQuery {
     pub product: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub status: Option<String>,
+    /// Statuses to match; each is sent as a repeated `status=` parameter.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub include_artifacts: Option<bool>,
+    /// Only return releases with an artifact for this platform (e.g.
+    /// `"linux"`). This does not imply `include_artifacts`; set both to get
+    /// the matching releases' artifact summaries too.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub limit: Option<i32>,
+    pub platform: Option<String>,
+    /// Only return releases modified at or after this Unix timestamp.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub offset: Option<i32>,
+    pub updated_since: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    /// Extra `(name, value)` parameters sent after the typed ones, for filters
+    /// this crate does not model yet. Values are URL-encoded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_query: Vec<(String, String)>,
+}
+
+impl ReleaseListQuery {
+    /// Add a status to match, keeping any already set.
+    pub fn with_status(mut self, status: impl Into<String>) -> Self {
+        self.status.push(status.into());
+        self
+    }
+}
+
+/// One product with releases, as listed by `Client::list_products`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProductSummary {
+    pub name: String,
+    /// Version of the most recent release, if the product has any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_version: Option<String>,
+    pub release_count: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProductListResponse {
+    pub products: Vec<ProductSummary>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct ReleaseListResponse {
     pub releases: Vec<ReleaseResponse>,
-    pub limit: i64,
-    pub offset: i64,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+/// A release from `Client::list_releases_sparse`, holding only the fields
+/// the server returned.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct SparseRelease {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<ReleaseId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "crate::timestamp::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub created_at: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "crate::timestamp::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub published_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<Vec<ArtifactSummary>>,
+    #[serde(
+        default,
+        deserialize_with = "crate::timestamp::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub updated_at: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SparseReleaseListResponse {
+    pub releases: Vec<SparseRelease>,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+/// A release lifecycle event from `Client::subscribe_release_events`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ReleaseEvent {
+    /// SSE event id, used as `Last-Event-ID` when the client reconnects.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Event kind such as `release.published`; taken from the SSE `event:`
+    /// field when the payload has no `type`.
+    #[serde(rename = "type", default)]
+    pub event_type: String,
+    pub release: ReleaseResponse,
 }
@@ -0,0 +1,40 @@
+/// Position of the next page, for endpoints using either offset or cursor
+/// pagination.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PageToken {
+    /// Offset of the first item of the next page.
+    Offset(u32),
+    /// Opaque cursor returned by the server.
+    Cursor(String),
+}
+
+/// One page of a list endpoint, see `Client::releases_page` and
+/// `Client::users_page`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Token for the following page; `None` on the last page.
+    pub next: Option<PageToken>,
+}
+
+impl<T> Page<T> {
+    /// Build a page of an offset-paginated endpoint. A page shorter than
+    /// `limit` is the last one, as is a page whose next offset would not fit
+    /// in a `u32`.
+    pub(crate) fn from_offset(items: Vec<T>, offset: u32, limit: u32) -> Self {
+        let next = u32::try_from(items.len())
+            .ok()
+            .filter(|count| *count > 0 && *count >= limit)
+            .and_then(|count| offset.checked_add(count))
+            .map(PageToken::Offset);
+        Self { items, next }
+    }
+
+    /// Build a page of a cursor-paginated endpoint.
+    pub(crate) fn from_cursor(items: Vec<T>, cursor: Option<String>) -> Self {
+        Self {
+            items,
+            next: cursor.map(PageToken::Cursor),
+        }
+    }
+}
@@ -0,0 +1,410 @@
+use ureq::RequestBuilder;
+use ureq::http::{Method, Uri};
+use ureq::typestate::{WithBody, WithoutBody};
+
+use crate::client::{
+    Client, audit_query_pairs, customer_query_pairs, entitlement_query_pairs, user_query_pairs,
+    with_query,
+};
+use crate::error::{Error, Result};
+use crate::id::{CustomerId, ReleaseId, UserId};
+use crate::models::*;
+use crate::redact::redacted_header;
+
+/// An API call to check with `Client::prepare` without sending it.
+///
+/// The client's own methods build their requests from the same description,
+/// so a prepared request matches what the method sends.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum Operation<'a> {
+    HealthCheck,
+    ReadyCheck,
+    LiveCheck,
+    OpenApi,
+    ListReleases(&'a ReleaseListQuery),
+    ListReleasesSparse {
+        query: &'a ReleaseListQuery,
+        fields: &'a [&'a str],
+    },
+    ListProducts,
+    CreateRelease(&'a ReleaseCreateRequest),
+    DeleteRelease(ReleaseId),
+    PublishRelease(ReleaseId),
+    UnpublishRelease(ReleaseId),
+    RegisterArtifact {
+        release_id: ReleaseId,
+        body: &'a ArtifactRegisterRequest,
+    },
+    PresignArtifactUpload {
+        release_id: ReleaseId,
+        body: &'a ArtifactPresignRequest,
+    },
+    CreateDownloadToken(&'a DownloadTokenRequest),
+    ResolveDownloadToken {
+        token: &'a str,
+        purpose: Option<DownloadPurpose>,
+    },
+    ListAuditEvents(&'a AuditEventListQuery),
+    ListReleaseAuditEvents {
+        release_id: ReleaseId,
+        query: &'a AuditEventListQuery,
+    },
+    ListCustomers(&'a AdminCustomerListQuery),
+    CreateCustomer(&'a AdminCreateCustomerRequest),
+    GetCustomer(CustomerId),
+    UpdateCustomer {
+        customer_id: CustomerId,
+        body: &'a AdminUpdateCustomerRequest,
+    },
+    ListUsers(&'a UserListQuery),
+    CreateUser(&'a UserCreateRequest),
+    GetUser(UserId),
+    PatchUser {
+        user_id: UserId,
+        body: &'a UserPatchRequest,
+    },
+    ReplaceUserMetadata {
+        user_id: UserId,
+        metadata: &'a serde_json::Value,
+    },
+    ReplaceGroups {
+        user_id: UserId,
+        body: &'a UserGroupsReplaceRequest,
+    },
+    ResetCredentials {
+        user_id: UserId,
+        body: &'a ResetCredentialsRequest,
+    },
+    ListEntitlements {
+        customer_id: CustomerId,
+        query: &'a EntitlementListQuery,
+    },
+    ListAllEntitlements(&'a GlobalEntitlementListQuery),
+    CreateEntitlement {
+        customer_id: CustomerId,
+        body: &'a EntitlementCreateRequest,
+    },
+    CreateEntitlementsBulk {
+        customer_id: CustomerId,
+        body: &'a EntitlementBulkCreateRequest,
+    },
+    UpdateEntitlement {
+        customer_id: CustomerId,
+        entitlement_id: &'a str,
+        body: &'a EntitlementUpdateRequest,
+    },
+    DeleteEntitlement {
+        customer_id: CustomerId,
+        entitlement_id: &'a str,
+    },
+    AdminCreateKey(&'a AdminCreateKeyRequest),
+    AdminRevokeKey(&'a AdminRevokeKeyRequest),
+    AdminRevokeKeys(&'a AdminRevokeKeysRequest),
+    AuthIntrospect,
+}
+
+/// A request as `Client::prepare` would send it.
+///
+/// Credential headers are masked. Headers added by a `request_signer` are
+/// not included, since signing may have side effects such as using up a
+/// nonce.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreparedRequest {
+    pub method: Method,
+    /// Absolute URL including the query string.
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    /// Serialized JSON body, for operations that send one.
+    pub body: Option<String>,
+}
+
+/// Method and URL of an operation after client-side validation.
+pub(crate) struct Endpoint {
+    pub(crate) method: Method,
+    pub(crate) url: String,
+}
+
+impl Client {
+    /// Run the client-side checks of `op` and return the request it would
+    /// send, without touching the network.
+    ///
+    /// Fails with `Error::Validation` when a body does not validate, the URL
+    /// does not parse, or an endpoint that needs credentials is called with
+    /// `Auth::None`.
+    pub fn prepare(&self, op: Operation<'_>) -> Result<PreparedRequest> {
+        let public = matches!(
+            op,
+            Operation::HealthCheck
+                | Operation::ReadyCheck
+                | Operation::LiveCheck
+                | Operation::OpenApi
+        );
+        if !public && self.auth_kind() == "none" {
+            return Err(validation("no credentials configured"));
+        }
+        let Endpoint { method, url } = self.endpoint(&op)?;
+        url.parse::<Uri>()
+            .map_err(|err| validation(format!("invalid request URL {}: {}", url, err)))?;
+        let body = body_json(&op)?;
+
+        let mut headers = match body {
+            Some(_) => header_pairs(
+                self.apply_headers(self.body_request(&method, &url))
+                    .header("Content-Type", "application/json; charset=utf-8"),
+            ),
+            None => header_pairs(self.apply_headers(self.bodyless_request(&method, &url))),
+        };
+        for (name, value) in &mut headers {
+            *value = redacted_header(name, value);
+        }
+        Ok(PreparedRequest {
+            method,
+            url,
+            headers,
+            body,
+        })
+    }
+
+    /// Validate `op` and resolve its method and URL. `prepare` and the
+    /// methods that send each call both build from this.
+    pub(crate) fn endpoint(&self, op: &Operation<'_>) -> Result<Endpoint> {
+        let (method, path, query) = match op {
+            Operation::HealthCheck => get("/health".to_string()),
+            Operation::ReadyCheck => get("/ready".to_string()),
+            Operation::LiveCheck => get("/live".to_string()),
+            Operation::OpenApi => get("/openapi.json".to_string()),
+            Operation::ListReleases(query) => (
+                Method::GET,
+                "/v1/releases".to_string(),
+                self.release_list_pairs(query),
+            ),
+            Operation::ListReleasesSparse { query, fields } => {
+                let mut pairs = self.release_list_pairs(query);
+                if !fields.is_empty() {
+                    pairs.push(("fields".to_string(), fields.join(",")));
+                }
+                (Method::GET, "/v1/releases".to_string(), pairs)
+            }
+            Operation::ListProducts => get("/v1/products".to_string()),
+            Operation::CreateRelease(body) => {
+                body.validate()?;
+                post("/v1/releases".to_string())
+            }
+            Operation::DeleteRelease(release_id) => (
+                Method::DELETE,
+                format!("/v1/releases/{}", release_id),
+                Vec::new(),
+            ),
+            Operation::PublishRelease(release_id) => {
+                post(format!("/v1/releases/{}/publish", release_id))
+            }
+            Operation::UnpublishRelease(release_id) => {
+                post(format!("/v1/releases/{}/unpublish", release_id))
+            }
+            Operation::RegisterArtifact { release_id, .. } => {
+                post(format!("/v1/releases/{}/artifacts", release_id))
+            }
+            Operation::PresignArtifactUpload { release_id, .. } => {
+                post(format!("/v1/releases/{}/artifacts/presign", release_id))
+            }
+            Operation::CreateDownloadToken(_) => post("/v1/downloads/token".to_string()),
+            Operation::ResolveDownloadToken { token, purpose } => (
+                Method::GET,
+                format!("/v1/downloads/{}", token),
+                purpose
+                    .iter()
+                    .map(|purpose| ("purpose".to_string(), purpose.as_str().to_string()))
+                    .collect(),
+            ),
+            Operation::ListAuditEvents(query) => (
+                Method::GET,
+                "/v1/admin/audit-events".to_string(),
+                audit_query_pairs(query, self.page_limit(query.limit)),
+            ),
+            Operation::ListReleaseAuditEvents { release_id, query } => (
+                Method::GET,
+                format!("/v1/releases/{}/audit-events", release_id),
+                audit_query_pairs(query, self.page_limit(query.limit)),
+            ),
+            Operation::ListCustomers(query) => (
+                Method::GET,
+                "/v1/admin/customers".to_string(),
+                customer_query_pairs(query, self.page_limit(query.limit)),
+            ),
+            Operation::CreateCustomer(_) => post("/v1/admin/customers".to_string()),
+            Operation::GetCustomer(customer_id) => {
+                get(format!("/v1/admin/customers/{}", customer_id))
+            }
+            Operation::UpdateCustomer { customer_id, .. } => (
+                Method::PATCH,
+                format!("/v1/admin/customers/{}", customer_id),
+                Vec::new(),
+            ),
+            Operation::ListUsers(query) => (
+                Method::GET,
+                "/v1/admin/users".to_string(),
+                user_query_pairs(query, self.page_limit(query.limit)),
+            ),
+            Operation::CreateUser(_) => post("/v1/admin/users".to_string()),
+            Operation::GetUser(user_id) => get(format!("/v1/admin/users/{}", user_id)),
+            Operation::PatchUser { user_id, .. } => (
+                Method::PATCH,
+                format!("/v1/admin/users/{}", user_id),
+                Vec::new(),
+            ),
+            Operation::ReplaceUserMetadata { user_id, .. } => (
+                Method::PUT,
+                format!("/v1/admin/users/{}/metadata", user_id),
+                Vec::new(),
+            ),
+            Operation::ReplaceGroups { user_id, .. } => (
+                Method::PUT,
+                format!("/v1/admin/users/{}/groups", user_id),
+                Vec::new(),
+            ),
+            Operation::ResetCredentials { user_id, .. } => {
+                post(format!("/v1/admin/users/{}/reset-credentials", user_id))
+            }
+            Operation::ListEntitlements { customer_id, query } => (
+                Method::GET,
+                format!("/v1/admin/customers/{}/entitlements", customer_id),
+                entitlement_query_pairs(query, None, self.page_limit(query.limit)),
+            ),
+            Operation::ListAllEntitlements(query) => {
+                let scoped = EntitlementListQuery {
+                    product: query.product.clone(),
+                    active_at: query.active_at,
+                    include_expired: query.include_expired,
+                    limit: query.limit,
+                    offset: query.offset,
+                    extra_query: query.extra_query.clone(),
+                };
+                (
+                    Method::GET,
+                    "/v1/admin/entitlements".to_string(),
+                    entitlement_query_pairs(
+                        &scoped,
+                        query.customer_id.as_ref(),
+                        self.page_limit(query.limit),
+                    ),
+                )
+            }
+            Operation::CreateEntitlement { customer_id, body } => {
+                body.validate()?;
+                post(format!("/v1/admin/customers/{}/entitlements", customer_id))
+            }
+            Operation::CreateEntitlementsBulk { customer_id, body } => {
+                for entitlement in &body.entitlements {
+                    entitlement.validate()?;
+                }
+                post(format!(
+                    "/v1/admin/customers/{}/entitlements/bulk",
+                    customer_id
+                ))
+            }
+            Operation::UpdateEntitlement {
+                customer_id,
+                entitlement_id,
+                body,
+            } => {
+                body.validate()?;
+                (
+                    Method::PATCH,
+                    format!(
+                        "/v1/admin/customers/{}/entitlements/{}",
+                        customer_id, entitlement_id
+                    ),
+                    Vec::new(),
+                )
+            }
+            Operation::DeleteEntitlement {
+                customer_id,
+                entitlement_id,
+            } => (
+                Method::DELETE,
+                format!(
+                    "/v1/admin/customers/{}/entitlements/{}",
+                    customer_id, entitlement_id
+                ),
+                Vec::new(),
+            ),
+            Operation::AdminCreateKey(_) => post("/v1/admin/keys".to_string()),
+            Operation::AdminRevokeKey(_) => post("/v1/admin/keys/revoke".to_string()),
+            Operation::AdminRevokeKeys(_) => post("/v1/admin/keys/revoke-batch".to_string()),
+            Operation::AuthIntrospect => post("/v1/auth/introspect".to_string()),
+        };
+        Ok(Endpoint {
+            method,
+            url: with_query(self.url(&path), &query),
+        })
+    }
+
+    /// Request for `endpoint` without a body, with the client's headers.
+    pub(crate) fn endpoint_request(&self, endpoint: &Endpoint) -> RequestBuilder<WithoutBody> {
+        self.apply_headers(self.bodyless_request(&endpoint.method, &endpoint.url))
+    }
+
+    /// Request for `endpoint` with a body, with the client's headers.
+    pub(crate) fn endpoint_body_request(&self, endpoint: &Endpoint) -> RequestBuilder<WithBody> {
+        self.apply_headers(self.body_request(&endpoint.method, &endpoint.url))
+    }
+}
+
+type Parts = (Method, String, Vec<(String, String)>);
+
+fn get(path: String) -> Parts {
+    (Method::GET, path, Vec::new())
+}
+
+fn post(path: String) -> Parts {
+    (Method::POST, path, Vec::new())
+}
+
+/// The JSON body `op` sends, if any.
+fn body_json(op: &Operation<'_>) -> Result<Option<String>> {
+    let body = match op {
+        Operation::CreateRelease(body) => json(body),
+        Operation::RegisterArtifact { body, .. } => json(body),
+        Operation::PresignArtifactUpload { body, .. } => json(body),
+        Operation::CreateDownloadToken(body) => json(body),
+        Operation::CreateCustomer(body) => json(body),
+        Operation::UpdateCustomer { body, .. } => json(body),
+        Operation::CreateUser(body) => json(body),
+        Operation::PatchUser { body, .. } => json(body),
+        Operation::ReplaceUserMetadata { metadata, .. } => json(metadata),
+        Operation::ReplaceGroups { body, .. } => json(body),
+        Operation::ResetCredentials { body, .. } => json(body),
+        Operation::CreateEntitlement { body, .. } => json(body),
+        Operation::CreateEntitlementsBulk { body, .. } => json(body),
+        Operation::UpdateEntitlement { body, .. } => json(body),
+        Operation::AdminCreateKey(body) => json(body),
+        Operation::AdminRevokeKey(body) => json(body),
+        Operation::AdminRevokeKeys(body) => json(body),
+        _ => return Ok(None),
+    };
+    body.map(Some)
+}
+
+fn json<B: serde::Serialize>(body: &B) -> Result<String> {
+    serde_json::to_string(body)
+        .map_err(|err| validation(format!("request body cannot be serialized: {}", err)))
+}
+
+fn validation(message: impl Into<String>) -> Error {
+    Error::Validation {
+        message: message.into(),
+    }
+}
+
+fn header_pairs<B>(request: RequestBuilder<B>) -> Vec<(String, String)> {
+    request
+        .headers_ref()
+        .into_iter()
+        .flatten()
+        .map(|(name, value)| {
+            let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+            (name.as_str().to_string(), value)
+        })
+        .collect()
+}
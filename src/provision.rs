@@ -0,0 +1,104 @@
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::models::*;
+
+/// Everything `Client::provision_customer` creates for a new customer.
+///
+/// The `customer_id` fields of `api_key` and `admin_user` are replaced with
+/// the id of the newly created customer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomerProvisionSpec {
+    pub customer: AdminCreateCustomerRequest,
+    pub api_key: Option<AdminCreateKeyRequest>,
+    pub entitlements: Vec<EntitlementCreateRequest>,
+    pub admin_user: Option<UserCreateRequest>,
+}
+
+/// Resources created by `Client::provision_customer`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomerProvisionResult {
+    pub customer: AdminCreateCustomerResponse,
+    pub api_key: Option<AdminCreateKeyResponse>,
+    pub entitlements: Vec<EntitlementResponse>,
+    pub admin_user: Option<UserResponse>,
+}
+
+impl Client {
+    /// Create a customer together with its API key, entitlements, and admin
+    /// user.
+    ///
+    /// When a step after the customer creation fails, the resources created
+    /// so far are rolled back on a best-effort basis: the key is revoked, the
+    /// entitlements are deleted, and the customer is suspended (the API cannot
+    /// delete customers). The failure is returned as
+    /// `Error::ProvisionFailed`, listing any rollback steps that failed too.
+    pub fn provision_customer(
+        &self,
+        spec: &CustomerProvisionSpec,
+    ) -> Result<CustomerProvisionResult> {
+        let customer = self.admin_create_customer(&spec.customer)?;
+        let mut result = CustomerProvisionResult {
+            customer,
+            api_key: None,
+            entitlements: Vec::new(),
+            admin_user: None,
+        };
+        match self.provision_resources(spec, &mut result) {
+            Ok(()) => Ok(result),
+            Err(error) => Err(Error::ProvisionFailed {
+                customer_id: result.customer.id.to_string(),
+                cleanup_errors: self.roll_back_provision(&result),
+                source: Box::new(error),
+            }),
+        }
+    }
+
+    fn provision_resources(
+        &self,
+        spec: &CustomerProvisionSpec,
+        result: &mut CustomerProvisionResult,
+    ) -> Result<()> {
+        let customer_id = result.customer.id.clone();
+        if let Some(key) = &spec.api_key {
+            let key = AdminCreateKeyRequest {
+                customer_id: customer_id.clone(),
+                ..key.clone()
+            };
+            result.api_key = Some(self.admin_create_key(&key)?);
+        }
+        for entitlement in &spec.entitlements {
+            let created = self.create_entitlement(&customer_id, entitlement)?;
+            result.entitlements.push(created);
+        }
+        if let Some(user) = &spec.admin_user {
+            let user = UserCreateRequest {
+                customer_id,
+                ..user.clone()
+            };
+            result.admin_user = Some(self.create_user(&user)?);
+        }
+        Ok(())
+    }
+
+    /// Undo a partial provisioning, returning the errors of failed steps.
+    fn roll_back_provision(&self, result: &CustomerProvisionResult) -> Vec<Error> {
+        let customer_id = &result.customer.id;
+        let mut errors = Vec::new();
+        if let Some(key) = &result.api_key {
+            let revoke = AdminRevokeKeyRequest {
+                api_key_id: key.api_key_id.clone(),
+            };
+            errors.extend(self.admin_revoke_key(&revoke).err());
+        }
+        for entitlement in &result.entitlements {
+            errors.extend(self.delete_entitlement(customer_id, &entitlement.id).err());
+        }
+        let suspend = AdminUpdateCustomerRequest {
+            name: None,
+            plan: None,
+            suspended: Some(true),
+        };
+        errors.extend(self.update_customer(customer_id, &suspend).err());
+        errors
+    }
+}
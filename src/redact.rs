@@ -0,0 +1,69 @@
+use serde_json::Value;
+
+const REDACTED: &str = "[redacted]";
+
+/// Field names whose values are masked before a payload leaves the client.
+const SECRET_FIELDS: &[&str] = &[
+    "api_key",
+    "access_token",
+    "refresh_token",
+    "token",
+    "password",
+    "secret",
+    "client_secret",
+    "authorization",
+    "proxy-authorization",
+    "reset_link",
+];
+
+/// Prefix of Releasy header names; those ending in `-key` or `-token` carry
+/// credentials and are masked wherever they appear as keys.
+const SECRET_PREFIX: &str = "x-releasy-";
+
+/// Mask values of known secret fields, recursing into nested objects and arrays.
+pub(crate) fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_secret_field(key) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+/// Render a response body for logging: JSON is redacted, anything else is
+/// summarized by length since it cannot be inspected for secrets.
+pub(crate) fn redacted_body(body: &str) -> String {
+    if body.is_empty() {
+        return "[empty]".to_string();
+    }
+    match serde_json::from_str::<Value>(body) {
+        Ok(mut value) => {
+            redact_json(&mut value);
+            value.to_string()
+        }
+        Err(_) => format!("[non-JSON body, {} bytes]", body.len()),
+    }
+}
+
+fn is_secret_field(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SECRET_FIELDS.contains(&name.as_str())
+        || (name.starts_with(SECRET_PREFIX) && (name.ends_with("-key") || name.ends_with("-token")))
+}
+
+/// Render a request header value for display, masking credentials such as
+/// `Authorization` and `x-releasy-admin-key`.
+pub(crate) fn redacted_header(name: &str, value: &str) -> String {
+    if is_secret_field(name) {
+        REDACTED.to_string()
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+/// Seconds of `min_per_sec` allowance a retry budget can bank.
+const BUDGET_WINDOW_SECS: f64 = 10.0;
+
+/// How transient failures are retried.
+///
+/// Only idempotent requests (GET, HEAD, PUT, DELETE, or any request carrying an
+/// `Idempotency-Key`) are retried, on connection errors, timeouts, and 429,
+/// 502, 503, or 504 responses. Backoff doubles from `initial_backoff` up to
+/// `max_backoff` and is randomized according to `jitter`; a `Retry-After`
+/// header in seconds takes precedence, capped at `max_backoff`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt.
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    #[serde(default)]
+    pub jitter: Jitter,
+}
+
+/// How a backoff delay is randomized so that many clients failing together do
+/// not retry in lockstep. `base` is the exponential backoff for the retry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Jitter {
+    /// Wait exactly `base`.
+    None,
+    /// Wait a random delay between zero and `base`.
+    #[default]
+    Full,
+    /// Wait half of `base` plus a random delay up to the other half.
+    Equal,
+    /// Wait a random delay between `initial_backoff` and three times the
+    /// previous delay, capped at `max_backoff`.
+    Decorrelated,
+}
+
+impl RetryPolicy {
+    /// Policy with `max_retries` retries and the default backoff.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Self::default()
+        }
+    }
+
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Delay before retry number `retry` (starting at 1), given the delay used
+    /// before the previous retry (`initial_backoff` for the first one).
+    pub fn delay(&self, retry: u32, previous: Duration, rng: &mut JitterRng) -> Duration {
+        let base = self.backoff(retry);
+        match self.jitter {
+            Jitter::None => base,
+            Jitter::Full => base.mul_f64(rng.next_f64()),
+            Jitter::Equal => base / 2 + (base / 2).mul_f64(rng.next_f64()),
+            Jitter::Decorrelated => {
+                let upper = previous
+                    .saturating_mul(3)
+                    .min(self.max_backoff)
+                    .max(self.initial_backoff);
+                let spread = upper - self.initial_backoff;
+                (self.initial_backoff + spread.mul_f64(rng.next_f64())).min(self.max_backoff)
+            }
+        }
+    }
+
+    /// Exponential backoff before retry number `retry`, without jitter.
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            jitter: Jitter::default(),
+        }
+    }
+}
+
+/// Small pseudo-random generator (SplitMix64) used for backoff jitter.
+///
+/// Clients seed it randomly; tests can use `JitterRng::seeded` to get
+/// reproducible delays from `RetryPolicy::delay`.
+#[derive(Clone, Debug)]
+pub struct JitterRng {
+    state: u64,
+}
+
+impl JitterRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn from_entropy() -> Self {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(0);
+        Self::seeded(hasher.finish())
+    }
+
+    /// Uniform value in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Process-wide allowance for retries, see `ClientBuilder::retry_budget`.
+///
+/// Every first attempt deposits `ratio` tokens and `min_per_sec` tokens
+/// accrue each second; each retry spends one token. At most ten seconds of
+/// `min_per_sec` (and never fewer than ten tokens) can be banked.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RetryBudget {
+    pub ratio: f64,
+    pub min_per_sec: u32,
+}
+
+#[derive(Debug)]
+struct Balance {
+    tokens: f64,
+    refilled_at: SystemTime,
+}
+
+/// Token bucket behind a `RetryBudget`, shared by clones of a client.
+#[derive(Clone, Debug)]
+pub(crate) struct BudgetTracker {
+    budget: RetryBudget,
+    balance: Arc<Mutex<Balance>>,
+}
+
+impl BudgetTracker {
+    pub(crate) fn new(budget: RetryBudget, now: SystemTime) -> Self {
+        Self {
+            budget,
+            balance: Arc::new(Mutex::new(Balance {
+                tokens: f64::from(budget.min_per_sec),
+                refilled_at: now,
+            })),
+        }
+    }
+
+    /// Credit the budget for a first attempt.
+    pub(crate) fn deposit(&self, now: SystemTime) {
+        self.update(now, |tokens| {
+            *tokens += self.budget.ratio;
+        });
+    }
+
+    /// Spend one token for a retry; `false` when the budget is exhausted.
+    pub(crate) fn try_withdraw(&self, now: SystemTime) -> bool {
+        let mut allowed = false;
+        self.update(now, |tokens| {
+            if *tokens >= 1.0 {
+                *tokens -= 1.0;
+                allowed = true;
+            }
+        });
+        allowed
+    }
+
+    fn update(&self, now: SystemTime, change: impl FnOnce(&mut f64)) {
+        let capacity = f64::from(self.budget.min_per_sec.max(1)) * BUDGET_WINDOW_SECS;
+        let mut balance = self
+            .balance
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        // A clock moving backwards refills nothing.
+        let elapsed = now
+            .duration_since(balance.refilled_at)
+            .unwrap_or_default()
+            .as_secs_f64();
+        balance.refilled_at = balance.refilled_at.max(now);
+        balance.tokens += elapsed * f64::from(self.budget.min_per_sec);
+        change(&mut balance.tokens);
+        balance.tokens = balance.tokens.min(capacity);
+    }
+}
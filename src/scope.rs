@@ -0,0 +1,112 @@
+use std::borrow::Borrow;
+
+use crate::client::Client;
+use crate::error::Result;
+use crate::id::CustomerId;
+use crate::models::*;
+
+/// Customer-scoped view of a `Client`, obtained via `Client::for_customer`.
+///
+/// Wraps the per-customer admin endpoints with the customer id pre-bound.
+#[derive(Clone, Debug)]
+pub struct CustomerScope<'a> {
+    client: &'a Client,
+    customer_id: CustomerId,
+}
+
+impl<'a> CustomerScope<'a> {
+    pub(crate) fn new(client: &'a Client, customer_id: impl Into<CustomerId>) -> Self {
+        Self {
+            client,
+            customer_id: customer_id.into(),
+        }
+    }
+
+    /// Return the bound customer id.
+    pub fn customer_id(&self) -> &CustomerId {
+        &self.customer_id
+    }
+
+    /// Return the underlying client.
+    pub fn client(&self) -> &'a Client {
+        self.client
+    }
+
+    /// Fetch the bound customer.
+    pub fn get_customer(&self) -> Result<AdminCustomerResponse> {
+        self.client.get_customer(&self.customer_id)
+    }
+
+    /// Update the bound customer.
+    pub fn update_customer(
+        &self,
+        body: &AdminUpdateCustomerRequest,
+    ) -> Result<AdminCustomerResponse> {
+        self.client.update_customer(&self.customer_id, body)
+    }
+
+    /// List the customer's entitlements.
+    pub fn list_entitlements(
+        &self,
+        query: impl Borrow<EntitlementListQuery>,
+    ) -> Result<EntitlementListResponse> {
+        let query = query.borrow();
+        self.client.list_entitlements(&self.customer_id, query)
+    }
+
+    /// Create an entitlement for the customer.
+    pub fn create_entitlement(
+        &self,
+        body: &EntitlementCreateRequest,
+    ) -> Result<EntitlementResponse> {
+        self.client.create_entitlement(&self.customer_id, body)
+    }
+
+    /// Create several entitlements for the customer in one request.
+    pub fn create_entitlements_bulk(
+        &self,
+        body: &EntitlementBulkCreateRequest,
+    ) -> Result<EntitlementBulkCreateResponse> {
+        self.client
+            .create_entitlements_bulk(&self.customer_id, body)
+    }
+
+    /// Update one of the customer's entitlements.
+    pub fn update_entitlement(
+        &self,
+        entitlement_id: &str,
+        body: &EntitlementUpdateRequest,
+    ) -> Result<EntitlementResponse> {
+        self.client
+            .update_entitlement(&self.customer_id, entitlement_id, body)
+    }
+
+    /// Delete one of the customer's entitlements.
+    pub fn delete_entitlement(&self, entitlement_id: &str) -> Result<()> {
+        self.client
+            .delete_entitlement(&self.customer_id, entitlement_id)
+    }
+
+    /// List the customer's users. Any `customer_id` on the query is overridden.
+    pub fn list_users(&self, query: impl Borrow<UserListQuery>) -> Result<UserListResponse> {
+        let query = query.borrow();
+        let query = UserListQuery {
+            customer_id: Some(self.customer_id.clone()),
+            ..query.clone()
+        };
+        self.client.list_users(&query)
+    }
+
+    /// List the customer's audit events. Any `customer_id` on the query is overridden.
+    pub fn list_audit_events(
+        &self,
+        query: impl Borrow<AuditEventListQuery>,
+    ) -> Result<AuditEventListResponse> {
+        let query = query.borrow();
+        let query = AuditEventListQuery {
+            customer_id: Some(self.customer_id.clone()),
+            ..query.clone()
+        };
+        self.client.list_audit_events(&query)
+    }
+}
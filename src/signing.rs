@@ -0,0 +1,38 @@
+use std::fmt;
+use std::sync::Arc;
+
+/// Request details handed to a request signer just before sending.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignableRequest<'a> {
+    /// HTTP method, e.g. `POST`.
+    pub method: &'a str,
+    /// Path and query string, e.g. `/v1/releases?product=demo`.
+    pub path: &'a str,
+    /// Exact body bytes; empty for requests without a body and for streamed
+    /// multipart uploads.
+    pub body: &'a [u8],
+}
+
+type SignerFn = dyn Fn(&SignableRequest<'_>) -> Vec<(String, String)> + Send + Sync;
+
+/// Shared signer closure installed via `ClientBuilder::request_signer`.
+#[derive(Clone)]
+pub(crate) struct RequestSigner(Arc<SignerFn>);
+
+impl RequestSigner {
+    pub(crate) fn new(
+        signer: impl Fn(&SignableRequest<'_>) -> Vec<(String, String)> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(signer))
+    }
+
+    pub(crate) fn headers(&self, request: &SignableRequest<'_>) -> Vec<(String, String)> {
+        (self.0)(request)
+    }
+}
+
+impl fmt::Debug for RequestSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RequestSigner(..)")
+    }
+}
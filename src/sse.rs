@@ -0,0 +1,91 @@
+use std::io::{self, BufRead};
+use std::time::Duration;
+
+/// One dispatched server-sent event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SseEvent {
+    /// Last event id seen on the stream, which per the SSE spec carries over
+    /// to events without their own `id:` field.
+    pub(crate) id: Option<String>,
+    pub(crate) event: Option<String>,
+    /// `data:` lines joined with `\n`.
+    pub(crate) data: String,
+}
+
+/// Incremental `text/event-stream` parser over a buffered reader.
+pub(crate) struct SseReader<R> {
+    reader: R,
+    last_id: Option<String>,
+    retry: Option<Duration>,
+}
+
+impl<R: BufRead> SseReader<R> {
+    pub(crate) fn new(reader: R, last_id: Option<String>) -> Self {
+        Self {
+            reader,
+            last_id,
+            retry: None,
+        }
+    }
+
+    /// Id to send as `Last-Event-ID` when reconnecting.
+    pub(crate) fn last_id(&self) -> Option<&str> {
+        self.last_id.as_deref()
+    }
+
+    /// Reconnection delay requested by the server through `retry:`.
+    pub(crate) fn retry(&self) -> Option<Duration> {
+        self.retry
+    }
+
+    /// Read up to the next event with data. Returns `Ok(None)` at the end of
+    /// the stream; a trailing event without its blank line is dropped, as the
+    /// spec requires.
+    pub(crate) fn next_event(&mut self) -> io::Result<Option<SseEvent>> {
+        let mut event = None;
+        let mut data: Option<String> = None;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                if let Some(data) = data.take() {
+                    return Ok(Some(SseEvent {
+                        id: self.last_id.clone(),
+                        event: event.take(),
+                        data,
+                    }));
+                }
+                event = None;
+                continue;
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line, ""),
+            };
+            match field {
+                "data" => match &mut data {
+                    Some(data) => {
+                        data.push('\n');
+                        data.push_str(value);
+                    }
+                    None => data = Some(value.to_string()),
+                },
+                "event" => event = Some(value.to_string()),
+                "id" if !value.contains('\0') => self.last_id = Some(value.to_string()),
+                "retry" => {
+                    if let Ok(millis) = value.parse::<u64>() {
+                        self.retry = Some(Duration::from_millis(millis));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
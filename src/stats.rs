@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Snapshot of request and connection counters for a client.
+///
+/// Counters are shared across clones of a `Client` and are updated with
+/// relaxed atomics, so they give a rough picture rather than exact figures.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClientStats {
+    /// Requests issued by the client, counting each retry attempt.
+    pub requests_total: u64,
+    /// Connections opened rather than reused from the pool. Stays at zero
+    /// when the client was built with a custom `Agent`.
+    pub new_connections: u64,
+    /// Attempts that repeated an earlier failed request.
+    pub retries: u64,
+}
+
+impl ClientStats {
+    /// Requests that did not need a new connection.
+    pub fn reused_connections(&self) -> u64 {
+        self.requests_total.saturating_sub(self.new_connections)
+    }
+}
+
+/// Round-trip latency summary produced by `Client::measure_latency`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LatencyStats {
+    pub samples: u32,
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    /// Median sample (nearest rank, so the lower middle for even counts).
+    pub p50: Duration,
+}
+
+impl LatencyStats {
+    /// Summarize a non-empty set of samples.
+    pub(crate) fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let count = samples.len() as u32;
+        let total: Duration = samples.iter().sum();
+        Self {
+            samples: count,
+            min: samples[0],
+            max: samples[samples.len() - 1],
+            mean: total / count,
+            p50: samples[(samples.len() - 1) / 2],
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    requests_total: AtomicU64,
+    new_connections: AtomicU64,
+    retries: AtomicU64,
+}
+
+/// Shared, cheaply clonable handle to a client's counters.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Stats {
+    counters: Arc<Counters>,
+}
+
+impl Stats {
+    pub(crate) fn record_request(&self) {
+        self.counters.requests_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_new_connection(&self) {
+        self.counters
+            .new_connections
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_retry(&self) {
+        self.counters.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> ClientStats {
+        ClientStats {
+            requests_total: self.counters.requests_total.load(Ordering::Relaxed),
+            new_connections: self.counters.new_connections.load(Ordering::Relaxed),
+            retries: self.counters.retries.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,316 @@
+//! In-process mock of the Releasy API for downstream integration tests.
+//!
+//! Enabled by the `test-utils` feature. `MockReleasy` listens on a local port,
+//! answers each request with the canned response registered for its method
+//! and path, and records every request for later assertions.
+//!
+//! ```
+//! use releasy_client::test_utils::{MockReleasy, MockResponse};
+//! use releasy_client::{Auth, Client};
+//! use ureq::http::Method;
+//!
+//! let mock = MockReleasy::start().unwrap();
+//! mock.respond(
+//!     Method::GET,
+//!     "/health",
+//!     MockResponse::json(200, &serde_json::json!({ "status": "ok" })),
+//! );
+//!
+//! let client = Client::new(mock.base_url(), Auth::None).unwrap();
+//! assert_eq!(client.health_check().unwrap().status, "ok");
+//! assert_eq!(mock.requests()[0].path, "/health");
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use ureq::http::Method;
+
+/// A canned response served by `MockReleasy`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl MockResponse {
+    /// Response with `status` and an empty body.
+    pub fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// Response with `status` and `body` serialized as JSON.
+    pub fn json<T: Serialize + ?Sized>(status: u16, body: &T) -> Self {
+        let body = serde_json::to_vec(body).expect("mock response body serializes");
+        Self::new(status)
+            .header("Content-Type", "application/json")
+            .body(body)
+    }
+
+    /// Response in the API's error envelope, as returned for failed calls.
+    pub fn error(status: u16, code: &str, message: &str) -> Self {
+        Self::json(
+            status,
+            &serde_json::json!({ "error": { "code": code, "message": message } }),
+        )
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+}
+
+/// A request received by `MockReleasy`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordedRequest {
+    pub method: Method,
+    /// Path without the query string.
+    pub path: String,
+    /// Raw query string, without the leading `?`.
+    pub query: Option<String>,
+    /// Header names are lowercase.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    /// First value of header `name`, matched case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Query parameters in order, without percent-decoding.
+    pub fn query_pairs(&self) -> Vec<(&str, &str)> {
+        self.query
+            .as_deref()
+            .unwrap_or("")
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+            .collect()
+    }
+
+    /// Decode the body as JSON.
+    pub fn json<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_slice(&self.body)
+    }
+}
+
+#[derive(Default)]
+struct State {
+    routes: HashMap<(Method, String), VecDeque<MockResponse>>,
+    requests: Vec<RecordedRequest>,
+}
+
+/// A local HTTP server standing in for the Releasy API.
+///
+/// Responses registered for the same method and path are served in order,
+/// and the last one keeps being served once the others are used up.
+/// Unregistered routes get a 404 in the API's error envelope. The server
+/// stops when the mock is dropped.
+pub struct MockReleasy {
+    base_url: String,
+    state: Arc<Mutex<State>>,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl MockReleasy {
+    /// Start a server on an ephemeral port of `127.0.0.1`.
+    pub fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        listener.set_nonblocking(true)?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        let state = Arc::new(Mutex::new(State::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let state = Arc::clone(&state);
+            let shutdown = Arc::clone(&shutdown);
+            thread::spawn(move || serve(listener, &state, &shutdown))
+        };
+        Ok(Self {
+            base_url,
+            state,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// Base URL to pass to `Client::new`.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Queue `response` for requests with `method` to `path` (without query).
+    pub fn respond(&self, method: Method, path: &str, response: MockResponse) {
+        self.lock()
+            .routes
+            .entry((method, path.to_string()))
+            .or_default()
+            .push_back(response);
+    }
+
+    /// Requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// Requests received so far with `method` to `path`.
+    pub fn requests_to(&self, method: Method, path: &str) -> Vec<RecordedRequest> {
+        self.lock()
+            .requests
+            .iter()
+            .filter(|request| request.method == method && request.path == path)
+            .cloned()
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for MockReleasy {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn serve(listener: TcpListener, state: &Mutex<State>, shutdown: &AtomicBool) {
+    while !shutdown.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                // Errors only affect this connection, which the client sees
+                // as a transport failure.
+                let _ = handle_connection(stream, state);
+            }
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(5));
+            }
+            Err(_) => return,
+        }
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let request = read_request(&mut stream)?;
+    let response = {
+        let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+        let key = (request.method.clone(), request.path.clone());
+        let response = match state.routes.get_mut(&key) {
+            Some(queue) if queue.len() > 1 => queue.pop_front(),
+            Some(queue) => queue.front().cloned(),
+            None => None,
+        };
+        let response = response.unwrap_or_else(|| {
+            MockResponse::error(
+                404,
+                "not_found",
+                &format!("no mock response for {} {}", request.method, request.path),
+            )
+        });
+        state.requests.push(request);
+        response
+    };
+    write_response(&mut stream, &response)
+}
+
+fn read_request(stream: &mut TcpStream) -> io::Result<RecordedRequest> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        if let Some(pos) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        let read = stream.read(&mut chunk)?;
+        if read == 0 {
+            return Err(invalid("connection closed before request headers"));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or("").split_whitespace();
+    let method = request_line
+        .next()
+        .and_then(|method| Method::from_bytes(method.as_bytes()).ok())
+        .ok_or_else(|| invalid("malformed request line"))?;
+    let target = request_line.next().unwrap_or("/");
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (target.to_string(), None),
+    };
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let mut body = buffer[header_end..].to_vec();
+    let content_length = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    while body.len() < content_length {
+        let read = stream.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+
+    Ok(RecordedRequest {
+        method,
+        path,
+        query,
+        headers,
+        body,
+    })
+}
+
+fn write_response(stream: &mut TcpStream, response: &MockResponse) -> io::Result<()> {
+    let reason = ureq::http::StatusCode::from_u16(response.status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("");
+    let mut head = format!("HTTP/1.1 {} {}\r\n", response.status, reason);
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        response.body.len()
+    ));
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
@@ -0,0 +1,102 @@
+use std::net::SocketAddr;
+
+use ureq::config::Config;
+use ureq::http::Uri;
+use ureq::unversioned::resolver::{DefaultResolver, ResolvedSocketAddrs, Resolver};
+use ureq::unversioned::transport::{
+    ConnectionDetails, Connector, DefaultConnector, NextTimeout, Transport,
+};
+
+use crate::stats::Stats;
+
+/// Capacity of `ResolvedSocketAddrs`; pushing past it panics.
+const MAX_ADDRS: usize = 16;
+
+/// Resolver that answers from static `host:port` overrides before falling
+/// back to DNS.
+#[derive(Debug, Default)]
+pub(crate) struct StaticResolver {
+    overrides: Vec<(String, SocketAddr)>,
+    fallback: DefaultResolver,
+}
+
+impl StaticResolver {
+    pub(crate) fn new(overrides: Vec<(String, SocketAddr)>) -> Self {
+        Self {
+            overrides,
+            fallback: DefaultResolver::default(),
+        }
+    }
+}
+
+impl Resolver for StaticResolver {
+    fn resolve(
+        &self,
+        uri: &Uri,
+        config: &Config,
+        timeout: NextTimeout,
+    ) -> Result<ResolvedSocketAddrs, ureq::Error> {
+        let host = uri.host().unwrap_or("");
+        let port = uri.port_u16().or(match uri.scheme_str() {
+            Some("https") => Some(443),
+            Some("http") => Some(80),
+            _ => None,
+        });
+        let mut resolved = self.empty();
+        let matching = self
+            .overrides
+            .iter()
+            .filter(|(name, addr)| {
+                name.eq_ignore_ascii_case(host) && port.is_none_or(|port| port == addr.port())
+            })
+            .take(MAX_ADDRS);
+        for (_, addr) in matching {
+            resolved.push(*addr);
+        }
+        if resolved.is_empty() {
+            // Lookup failures surface as I/O errors; report them as
+            // `HostNotFound` so callers can tell DNS from connection trouble.
+            return self
+                .fallback
+                .resolve(uri, config, timeout)
+                .map_err(|err| match err {
+                    ureq::Error::Io(_) => ureq::Error::HostNotFound,
+                    err => err,
+                });
+        }
+        Ok(resolved)
+    }
+}
+
+/// Connector that counts newly opened connections before delegating to the
+/// default connector chain. Pooled connections never reach the connector.
+#[derive(Debug)]
+pub(crate) struct CountingConnector {
+    inner: DefaultConnector,
+    stats: Stats,
+}
+
+impl CountingConnector {
+    pub(crate) fn new(stats: Stats) -> Self {
+        Self {
+            inner: DefaultConnector::new(),
+            stats,
+        }
+    }
+}
+
+impl Connector for CountingConnector {
+    type Out = Box<dyn Transport>;
+
+    fn connect(
+        &self,
+        details: &ConnectionDetails,
+        chained: Option<()>,
+    ) -> Result<Option<Self::Out>, ureq::Error> {
+        let transport = self.inner.connect(details, chained)?;
+        if transport.is_some() {
+            self.stats.record_new_connection();
+        }
+        Ok(transport)
+    }
+}
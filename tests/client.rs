@@ -2,14 +2,23 @@ use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::net::{TcpListener, TcpStream};
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex, Once};
 use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use releasy_client::{
-    AdminCreateCustomerRequest, AdminCustomerListQuery, AdminUpdateCustomerRequest, Auth, Client,
-    Error, ReleaseCreateRequest, ReleaseListQuery, ResetCredentialsRequest, UserCreateRequest,
-    UserGroupsReplaceRequest, UserListQuery, UserPatchRequest,
+    AdminCreateCustomerRequest, AdminCreateKeyRequest, AdminCustomerListQuery,
+    AdminRevokeKeysRequest, AdminUpdateCustomerRequest, ArtifactPresignResponse,
+    AuditEventListQuery, Auth, CancellationToken, Client, ClientConfig, ClientStats, Clock,
+    CustomerId, CustomerProvisionSpec, DownloadPurpose, DownloadStage, DownloadTokenRequest,
+    EntitlementBulkCreateRequest, EntitlementCreateRequest, EntitlementListQuery, Error,
+    GlobalEntitlementListQuery, Jitter, ManualClock, Operation, Page, PageToken, Platform,
+    ProductSummary, RangeDownload, RangeFallback, ReleaseCreateRequest, ReleaseEvent,
+    ReleaseListQuery, ReleaseListResponse, ResetCredentialsRequest, ResetCredentialsResponse,
+    RetryBudget, RetryPolicy, UserCreateRequest, UserGroupsReplaceRequest, UserListQuery,
+    UserPatchRequest, UserResponse,
 };
+use ureq::http::Method;
 
 struct RawRequest {
     method: String,
@@ -42,6 +51,26 @@ where
     (format!("http://{}", addr), handle)
 }
 
+fn spawn_server_sequence<F>(count: usize, mut handler: F) -> (String, thread::JoinHandle<()>)
+where
+    F: FnMut(RawRequest) -> ResponseSpec + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let addr = listener.local_addr().expect("local addr");
+    let handle = thread::spawn(move || {
+        for _ in 0..count {
+            let (mut stream, _) = listener.accept().expect("accept");
+            stream
+                .set_read_timeout(Some(Duration::from_secs(2)))
+                .expect("timeout");
+            let request = read_request(&mut stream);
+            let response = handler(request);
+            write_response(&mut stream, response);
+        }
+    });
+    (format!("http://{}", addr), handle)
+}
+
 fn read_request(stream: &mut TcpStream) -> RawRequest {
     let mut buffer = Vec::new();
     let mut temp = [0u8; 1024];
@@ -101,10 +130,15 @@ fn read_request(stream: &mut TcpStream) -> RawRequest {
 
 fn write_response(stream: &mut TcpStream, response: ResponseSpec) {
     let mut headers = response.headers;
-    headers.push((
-        "Content-Length".to_string(),
-        response.body.len().to_string(),
-    ));
+    let chunked = headers.iter().any(|(name, value)| {
+        name.eq_ignore_ascii_case("transfer-encoding") && value.eq_ignore_ascii_case("chunked")
+    });
+    if !chunked {
+        headers.push((
+            "Content-Length".to_string(),
+            response.body.len().to_string(),
+        ));
+    }
     headers.push(("Connection".to_string(), "close".to_string()));
 
     let mut response_text = String::new();
@@ -117,7 +151,16 @@ fn write_response(stream: &mut TcpStream, response: ResponseSpec) {
         response_text.push_str("\r\n");
     }
     response_text.push_str("\r\n");
-    response_text.push_str(&response.body);
+    if chunked {
+        for chunk in response.body.as_bytes().chunks(16) {
+            response_text.push_str(&format!("{:x}\r\n", chunk.len()));
+            response_text.push_str(std::str::from_utf8(chunk).expect("ascii body"));
+            response_text.push_str("\r\n");
+        }
+        response_text.push_str("0\r\n\r\n");
+    } else {
+        response_text.push_str(&response.body);
+    }
 
     stream
         .write_all(response_text.as_bytes())
@@ -174,6 +217,33 @@ fn health_check_happy_path() {
     let client = Client::new(base_url, Auth::None).unwrap();
     let response = client.health_check().unwrap();
     assert_eq!(response.status, "ok");
+    assert!(response.components.is_none());
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn health_check_parses_component_statuses() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.path, "/health");
+
+        let body = r#"{"status":"degraded","components":{"database":{"status":"ok","latency_ms":3},"storage":{"status":"unavailable"}}}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::None).unwrap();
+    let response = client.health_check().unwrap();
+    assert_eq!(response.status, "degraded");
+    let components = response.components.expect("components");
+    assert_eq!(components.len(), 2);
+    assert_eq!(components["database"].status, "ok");
+    assert_eq!(components["database"].latency_ms, Some(3));
+    assert_eq!(components["storage"].status, "unavailable");
+    assert_eq!(components["storage"].latency_ms, None);
 
     handle.join().expect("server join");
 }
@@ -254,11 +324,12 @@ fn list_customers_happy_path() {
 
     let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
     let query = AdminCustomerListQuery {
-        customer_id: Some("cust-1".to_string()),
+        customer_id: Some("cust-1".into()),
         name: Some("Acme".to_string()),
         plan: Some("pro".to_string()),
         limit: Some(100),
         offset: Some(10),
+        extra_query: Vec::new(),
     };
 
     let response = client.list_customers(&query).unwrap();
@@ -271,6 +342,31 @@ fn list_customers_happy_path() {
     handle.join().expect("server join");
 }
 
+#[test]
+fn list_customers_sends_large_offset() {
+    let (base_url, handle) = spawn_server(move |request| {
+        let (_, params) = parse_query(&request.path);
+        assert_eq!(params.get("offset"), Some(&"4000000000".to_string()));
+
+        let body = r#"{"customers":[],"limit":50,"offset":4000000000}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let query = AdminCustomerListQuery {
+        offset: Some(4_000_000_000),
+        ..Default::default()
+    };
+    let response = client.list_customers(&query).unwrap();
+    assert_eq!(response.offset, 4_000_000_000);
+
+    handle.join().expect("server join");
+}
+
 #[test]
 fn get_customer_not_found_returns_error() {
     let (base_url, handle) = spawn_server(move |request| {
@@ -342,6 +438,35 @@ fn update_customer_happy_path() {
     handle.join().expect("server join");
 }
 
+#[test]
+fn update_customer_keeps_plan_change_side_effects() {
+    let (base_url, handle) = spawn_server(move |_request| {
+        let body = r#"{"id":"cust-1","name":"Acme","created_at":1700000000,"plan":"enterprise","plan_changed_at":1700000500,"billing_preview":{"prorated_amount_cents":1250,"currency":"EUR"}}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = AdminUpdateCustomerRequest {
+        name: None,
+        plan: Some("enterprise".to_string()),
+        suspended: None,
+    };
+
+    let response = client.update_customer("cust-1", &request).unwrap();
+    assert_eq!(response.plan_changed_at, Some(1700000500));
+    assert_eq!(
+        response.extra["billing_preview"]["prorated_amount_cents"],
+        1250
+    );
+    assert_eq!(response.extra.len(), 1);
+
+    handle.join().expect("server join");
+}
+
 #[test]
 fn create_customer_with_idempotency_key_sends_header() {
     let (base_url, handle) = spawn_server(move |request| {
@@ -407,6 +532,43 @@ fn create_customer_without_idempotency_key_omits_header() {
     handle.join().expect("server join");
 }
 
+#[test]
+fn admin_revoke_keys_reports_partial_failures() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/admin/keys/revoke-batch");
+        let body_json: serde_json::Value =
+            serde_json::from_slice(&request.body).expect("json body");
+        assert_eq!(
+            body_json["api_key_ids"],
+            serde_json::json!(["key-1", "key-2"])
+        );
+
+        let body = r#"{"results":[{"api_key_id":"key-1","revoked":true},{"api_key_id":"key-2","revoked":false,"error":{"code":"not_found","message":"key missing"}}]}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = AdminRevokeKeysRequest {
+        api_key_ids: vec!["key-1".to_string(), "key-2".to_string()],
+    };
+    let response = client.admin_revoke_keys(&request).unwrap();
+    assert!(!response.all_revoked());
+    let failures: Vec<_> = response.failures().collect();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].api_key_id, "key-2");
+    assert_eq!(
+        failures[0].error.as_ref().map(|error| error.code.as_str()),
+        Some("not_found")
+    );
+
+    handle.join().expect("server join");
+}
+
 #[test]
 fn list_users_happy_path() {
     let (base_url, handle) = spawn_server(move |request| {
@@ -436,7 +598,7 @@ fn list_users_happy_path() {
 
     let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
     let query = UserListQuery {
-        customer_id: Some("cust-1".to_string()),
+        customer_id: Some("cust-1".into()),
         email: Some("alice".to_string()),
         status: Some("active".to_string()),
         keycloak_user_id: Some("kc-1".to_string()),
@@ -444,6 +606,7 @@ fn list_users_happy_path() {
         created_to: Some(1_700_001_000),
         limit: Some(25),
         cursor: Some("cursor-1".to_string()),
+        extra_query: Vec::new(),
     };
 
     let response = client.list_users(&query).unwrap();
@@ -485,7 +648,7 @@ fn create_user_happy_path() {
     let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
     let request = UserCreateRequest {
         email: "alice".to_string(),
-        customer_id: "cust-1".to_string(),
+        customer_id: "cust-1".into(),
         display_name: None,
         groups: Some(vec!["platform_admin".to_string()]),
         metadata: None,
@@ -499,6 +662,39 @@ fn create_user_happy_path() {
     handle.join().expect("server join");
 }
 
+#[test]
+fn create_user_validation_error_exposes_field_errors() {
+    let (base_url, handle) = spawn_server(move |_request| {
+        let body = r#"{"error":{"code":"validation_failed","message":"invalid user","details":[{"field":"email","message":"must be an email address"},{"field":"customer_id","message":"unknown customer"}]}}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 422 Unprocessable Entity".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = UserCreateRequest {
+        email: "alice".to_string(),
+        customer_id: "cust-missing".into(),
+        display_name: None,
+        groups: None,
+        metadata: None,
+        status: None,
+    };
+
+    let error = client.create_user(&request).expect_err("expected error");
+    assert_eq!(error.status(), Some(422));
+    let fields = error.field_errors();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[0].field, "email");
+    assert_eq!(fields[0].message, "must be an email address");
+    assert_eq!(fields[1].field, "customer_id");
+    assert_eq!(fields[1].message, "unknown customer");
+
+    handle.join().expect("server join");
+}
+
 #[test]
 fn create_user_with_idempotency_key_sends_header() {
     let (base_url, handle) = spawn_server(move |request| {
@@ -528,7 +724,7 @@ fn create_user_with_idempotency_key_sends_header() {
     let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
     let request = UserCreateRequest {
         email: "bob".to_string(),
-        customer_id: "cust-2".to_string(),
+        customer_id: "cust-2".into(),
         display_name: None,
         groups: Some(vec![]),
         metadata: None,
@@ -639,6 +835,39 @@ fn patch_user_happy_path() {
     handle.join().expect("server join");
 }
 
+#[test]
+fn patch_user_no_content_is_reported() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "PATCH");
+        ResponseSpec {
+            status_line: "HTTP/1.1 204 No Content".to_string(),
+            headers: vec![],
+            body: "".to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = UserPatchRequest {
+        display_name: Some("Alice".to_string()),
+        groups: None,
+        metadata: None,
+        status: None,
+    };
+
+    let error = client
+        .patch_user("user-1", &request)
+        .expect_err("expected error");
+    match error {
+        Error::UnexpectedNoContent { method, url } => {
+            assert_eq!(method, "PATCH");
+            assert!(url.ends_with("/v1/admin/users/user-1"), "{url}");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    handle.join().expect("server join");
+}
+
 #[test]
 fn replace_groups_happy_path() {
     let (base_url, handle) = spawn_server(move |request| {
@@ -674,6 +903,31 @@ fn replace_groups_happy_path() {
     handle.join().expect("server join");
 }
 
+#[test]
+fn replace_user_metadata_puts_the_full_object() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "PUT");
+        assert_eq!(request.path, "/v1/admin/users/user-1/metadata");
+        let body_json: serde_json::Value =
+            serde_json::from_slice(&request.body).expect("json body");
+        assert_eq!(body_json, serde_json::json!({ "team": "infra" }));
+
+        let body = r#"{"id":"user-1","keycloak_user_id":"kc-1","customer_id":"cust-1","email":"alice","status":"active","groups":[],"created_at":1700000000,"updated_at":1700002000,"metadata":{"team":"infra"}}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let metadata = serde_json::json!({ "team": "infra" });
+    let response = client.replace_user_metadata("user-1", &metadata).unwrap();
+    assert_eq!(response.metadata, Some(metadata));
+
+    handle.join().expect("server join");
+}
+
 #[test]
 fn reset_credentials_accepts_202() {
     let (base_url, handle) = spawn_server(move |request| {
@@ -699,134 +953,4733 @@ fn reset_credentials_accepts_202() {
         send_email: Some(true),
     };
 
-    client.reset_credentials("user-1", &request).expect("reset");
+    let response = client.reset_credentials("user-1", &request).expect("reset");
+    assert_eq!(response, None);
 
     handle.join().expect("server join");
 }
 
 #[test]
-fn list_releases_happy_path() {
+fn reset_credentials_returns_link_on_200() {
     let (base_url, handle) = spawn_server(move |request| {
-        assert_eq!(request.method, "GET");
-        let (path, params) = parse_query(&request.path);
-        assert_eq!(path, "/v1/releases");
-        assert_eq!(params.get("product"), Some(&"demo".to_string()));
-        assert_eq!(params.get("limit"), Some(&"10".to_string()));
-        assert_eq!(params.get("offset"), Some(&"0".to_string()));
-        assert_eq!(params.get("include_artifacts"), Some(&"true".to_string()));
-        assert_eq!(
-            request.headers.get("x-releasy-api-key"),
-            Some(&"test-key".to_string())
-        );
-
-        let body = r#"{"releases":[],"limit":10,"offset":0}"#;
+        assert_eq!(request.path, "/v1/admin/users/user-1/reset-credentials");
         ResponseSpec {
             status_line: "HTTP/1.1 200 OK".to_string(),
             headers: vec![("Content-Type".to_string(), "application/json".to_string())],
-            body: body.to_string(),
+            body: r#"{"reset_link":"https://id.example.com/reset/abc"}"#.to_string(),
         }
     });
 
-    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
-    let query = ReleaseListQuery {
-        product: Some("demo".to_string()),
-        limit: Some(10),
-        offset: Some(0),
-        include_artifacts: Some(true),
-        ..Default::default()
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = ResetCredentialsRequest {
+        send_email: Some(false),
     };
 
-    let response = client.list_releases(&query).unwrap();
-    assert_eq!(response.releases.len(), 0);
-    assert_eq!(response.limit, 10);
-    assert_eq!(response.offset, 0);
+    let response = client.reset_credentials("user-1", &request).expect("reset");
+    assert_eq!(
+        response,
+        Some(ResetCredentialsResponse {
+            reset_link: "https://id.example.com/reset/abc".to_string(),
+        })
+    );
 
     handle.join().expect("server join");
 }
 
 #[test]
-fn create_release_returns_api_error() {
+fn list_releases_appends_extra_query_pairs() {
     let (base_url, handle) = spawn_server(move |request| {
-        assert_eq!(request.method, "POST");
-        assert_eq!(request.path, "/v1/releases");
         assert_eq!(
-            request.headers.get("x-releasy-admin-key"),
-            Some(&"admin-key".to_string())
+            request.path,
+            "/v1/releases?product=demo&limit=5&channel=beta&region=eu%20west%261"
         );
-        let body_json: serde_json::Value =
-            serde_json::from_slice(&request.body).expect("json body");
-        assert_eq!(body_json["product"], "demo");
-        assert_eq!(body_json["version"], "1.0.0");
-
-        let body = r#"{"error":{"code":"release_conflict","message":"already exists"}}"#;
         ResponseSpec {
-            status_line: "HTTP/1.1 409 Conflict".to_string(),
+            status_line: "HTTP/1.1 200 OK".to_string(),
             headers: vec![("Content-Type".to_string(), "application/json".to_string())],
-            body: body.to_string(),
+            body: r#"{"releases":[],"limit":5,"offset":0}"#.to_string(),
         }
     });
 
     let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
-    let request = ReleaseCreateRequest {
-        product: "demo".to_string(),
-        version: "1.0.0".to_string(),
+    let query = ReleaseListQuery {
+        product: Some("demo".to_string()),
+        limit: Some(5),
+        extra_query: vec![
+            ("channel".to_string(), "beta".to_string()),
+            ("region".to_string(), "eu west&1".to_string()),
+        ],
+        ..Default::default()
     };
 
-    let error = client.create_release(&request).expect_err("expected error");
-    match error {
-        Error::Api { status, error, .. } => {
-            assert_eq!(status, 409);
-            let detail = error.expect("error body");
-            assert_eq!(detail.error.code, "release_conflict");
-            assert_eq!(detail.error.message, "already exists");
-        }
-        other => panic!("unexpected error: {other:?}"),
-    }
+    let response = client.list_releases(&query).unwrap();
+    assert!(response.releases.is_empty());
 
     handle.join().expect("server join");
 }
 
-#[test]
-fn upload_presigned_artifact_puts_file_body() {
-    let payload = b"releasy-upload-bytes";
-    let path = write_temp_file(payload);
-    let (base_url, handle) = spawn_server(move |request| {
-        assert_eq!(request.method, "PUT");
-        assert_eq!(request.path, "/upload");
-        assert_eq!(
-            request.headers.get("content-length"),
-            Some(&payload.len().to_string())
-        );
-        assert_eq!(request.body, payload);
+/// Walk every page through the common `Page` interface.
+fn collect_pages<T>(mut fetch: impl FnMut(Option<&PageToken>) -> Page<T>) -> Vec<T> {
+    let mut items = Vec::new();
+    let mut token = None;
+    loop {
+        let page = fetch(token.as_ref());
+        items.extend(page.items);
+        match page.next {
+            Some(next) => token = Some(next),
+            None => return items,
+        }
+    }
+}
 
+#[test]
+fn releases_page_advances_by_offset() {
+    let (base_url, handle) = spawn_server_sequence(2, |request| {
+        let (offset, body) = match request.path.as_str() {
+            "/v1/releases?limit=2" => (
+                0,
+                r#"{"id":"rel-1","product":"demo","version":"1.0.0","status":"published","created_at":1700000000},{"id":"rel-2","product":"demo","version":"1.1.0","status":"published","created_at":1700000001}"#,
+            ),
+            "/v1/releases?limit=2&offset=2" => (
+                2,
+                r#"{"id":"rel-3","product":"demo","version":"1.2.0","status":"draft","created_at":1700000002}"#,
+            ),
+            other => panic!("unexpected path: {other}"),
+        };
         ResponseSpec {
             status_line: "HTTP/1.1 200 OK".to_string(),
-            headers: vec![],
-            body: "".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: format!(r#"{{"releases":[{body}],"limit":2,"offset":{offset}}}"#),
         }
     });
 
-    let client = Client::new(base_url.clone(), Auth::None).unwrap();
-    let upload_url = format!("{}/upload", base_url);
-    client
-        .upload_presigned_artifact(&upload_url, &path)
-        .expect("upload");
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let query = ReleaseListQuery {
+        limit: Some(2),
+        ..Default::default()
+    };
+    let releases = collect_pages(|token| client.releases_page(&query, token).unwrap());
 
+    let ids: Vec<_> = releases.iter().map(|release| release.id.as_str()).collect();
+    assert_eq!(ids, ["rel-1", "rel-2", "rel-3"]);
     handle.join().expect("server join");
-    let _ = std::fs::remove_file(path);
 }
 
 #[test]
-fn upload_presigned_artifact_missing_file_returns_transport_error() {
-    let client = Client::new("http://localhost", Auth::None).unwrap();
-    let missing_path = std::env::temp_dir().join("releasy-client-missing-upload.bin");
-    let _ = std::fs::remove_file(&missing_path);
+fn releases_page_ends_when_next_offset_overflows() {
+    let (base_url, handle) = spawn_server(|_request| ResponseSpec {
+        status_line: "HTTP/1.1 200 OK".to_string(),
+        headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+        body: format!(
+            r#"{{"releases":[{{"id":"rel-1","product":"demo","version":"1.0.0","status":"published","created_at":1700000000}}],"limit":1,"offset":{}}}"#,
+            u32::MAX
+        ),
+    });
 
-    let error = client
-        .upload_presigned_artifact("http://localhost/upload", &missing_path)
-        .expect_err("expected error");
-    match error {
-        Error::Transport(ureq::Error::Io(_)) => {}
-        other => panic!("unexpected error: {other:?}"),
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let page = client
+        .releases_page(
+            &ReleaseListQuery::default(),
+            Some(&PageToken::Offset(u32::MAX)),
+        )
+        .unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.next, None);
+    handle.join().expect("server join");
+}
+
+#[test]
+fn users_page_advances_by_cursor() {
+    let (base_url, handle) = spawn_server_sequence(2, |request| {
+        let body = match request.path.as_str() {
+            "/v1/admin/users" => {
+                r#"{"users":[{"id":"user-1","keycloak_user_id":"kc-1","customer_id":"cust-1","email":"alice","status":"active","groups":[],"created_at":1700000000,"updated_at":1700000000}],"next_cursor":"cursor-2"}"#
+            }
+            "/v1/admin/users?cursor=cursor-2" => {
+                r#"{"users":[{"id":"user-2","keycloak_user_id":"kc-2","customer_id":"cust-1","email":"bob","status":"active","groups":[],"created_at":1700000000,"updated_at":1700000000}]}"#
+            }
+            other => panic!("unexpected path: {other}"),
+        };
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let users = collect_pages(|token| client.users_page(&UserListQuery::default(), token).unwrap());
+
+    let ids: Vec<_> = users.iter().map(|user| user.id.as_str()).collect();
+    assert_eq!(ids, ["user-1", "user-2"]);
+    handle.join().expect("server join");
+}
+
+#[test]
+fn page_token_kind_must_match_endpoint() {
+    let client = Client::new("http://localhost:1", Auth::None).unwrap();
+
+    let error = client
+        .releases_page(
+            &ReleaseListQuery::default(),
+            Some(&PageToken::Cursor("c".to_string())),
+        )
+        .expect_err("expected validation error");
+
+    assert!(matches!(error, Error::Validation { .. }), "{error:?}");
+}
+
+#[test]
+fn list_releases_happy_path() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "GET");
+        let (path, params) = parse_query(&request.path);
+        assert_eq!(path, "/v1/releases");
+        assert_eq!(params.get("product"), Some(&"demo".to_string()));
+        assert_eq!(params.get("limit"), Some(&"10".to_string()));
+        assert_eq!(params.get("offset"), Some(&"0".to_string()));
+        assert_eq!(params.get("include_artifacts"), Some(&"true".to_string()));
+        assert!(!params.contains_key("platform"));
+        assert!(!params.contains_key("fields"));
+        assert_eq!(
+            request.headers.get("x-releasy-api-key"),
+            Some(&"test-key".to_string())
+        );
+
+        let body = r#"{"releases":[],"limit":10,"offset":0}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let query = ReleaseListQuery {
+        product: Some("demo".to_string()),
+        limit: Some(10),
+        offset: Some(0),
+        include_artifacts: Some(true),
+        ..Default::default()
+    };
+
+    let response = client.list_releases(&query).unwrap();
+    assert_eq!(response.releases.len(), 0);
+    assert_eq!(response.limit, 10);
+    assert_eq!(response.offset, 0);
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn list_releases_with_artifacts_forces_include_artifacts() {
+    let (base_url, handle) = spawn_server(move |request| {
+        let (path, params) = parse_query(&request.path);
+        assert_eq!(path, "/v1/releases");
+        assert_eq!(params.get("product"), Some(&"demo".to_string()));
+        assert_eq!(params.get("include_artifacts"), Some(&"true".to_string()));
+
+        // The second release omits `artifacts`, as servers do when empty.
+        let body = r#"{"releases":[
+            {"id":"rel-1","product":"demo","version":"1.0.0","status":"published","created_at":1,
+             "artifacts":[{"id":"art-1","object_key":"demo/1.0.0/app","platform":"linux-x86_64","checksum":"abc","size":3}]},
+            {"id":"rel-2","product":"demo","version":"0.9.0","status":"draft","created_at":0}
+        ],"limit":50,"offset":0}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let query = ReleaseListQuery {
+        product: Some("demo".to_string()),
+        include_artifacts: Some(false),
+        ..Default::default()
+    };
+
+    let releases = client.list_releases_with_artifacts(&query).unwrap();
+    assert_eq!(releases.len(), 2);
+    assert_eq!(releases[0].release.id, "rel-1");
+    assert_eq!(releases[0].release.artifacts, None);
+    assert_eq!(releases[0].artifacts.len(), 1);
+    assert_eq!(releases[0].artifacts[0].id, "art-1");
+    assert_eq!(releases[1].release.id, "rel-2");
+    assert!(releases[1].artifacts.is_empty());
+
+    handle.join().expect("server join");
+}
+
+// Runs under both JSON backends; `serde_json` is the reference decoder.
+#[test]
+fn list_releases_parses_like_serde_json() {
+    const BODY: &str = r#"{"releases":[{"id":"rel-1","product":"demo","version":"1.2.0","status":"published","created_at":1700000000,"published_at":1700000100,"artifacts":[{"id":"art-1","object_key":"demo/1.2.0/app.tar.gz","platform":"linux","checksum":"abc123","size":4096}],"unknown_field":{"nested":[1,2.5,null,"\u00e9"]}},{"id":"rel-2","product":"demo","version":"1.3.0-rc.1","status":"draft","created_at":1700000200}],"limit":2,"offset":0}"#;
+    let (base_url, handle) = spawn_server(move |_request| ResponseSpec {
+        status_line: "HTTP/1.1 200 OK".to_string(),
+        headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+        body: BODY.to_string(),
+    });
+
+    let client = Client::new(base_url, Auth::None).unwrap();
+    let response = client.list_releases(ReleaseListQuery::default()).unwrap();
+    let expected: ReleaseListResponse = serde_json::from_str(BODY).unwrap();
+
+    assert_eq!(response, expected);
+    assert_eq!(response.releases.len(), 2);
+    handle.join().expect("server join");
+}
+
+#[test]
+fn list_releases_sends_platform_filter() {
+    let (base_url, handle) = spawn_server(move |request| {
+        let (path, params) = parse_query(&request.path);
+        assert_eq!(path, "/v1/releases");
+        assert_eq!(params.get("platform"), Some(&"linux".to_string()));
+        assert_eq!(params.get("include_artifacts"), Some(&"true".to_string()));
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"releases":[],"limit":50,"offset":0}"#.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let query = ReleaseListQuery {
+        platform: Some("linux".to_string()),
+        include_artifacts: Some(true),
+        ..Default::default()
+    };
+    client.list_releases(&query).unwrap();
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn list_releases_sends_sparse_fieldset() {
+    let (base_url, handle) = spawn_server(move |request| {
+        let (path, params) = parse_query(&request.path);
+        assert_eq!(path, "/v1/releases");
+        // `id,version,status`, percent-encoded by the query builder.
+        assert_eq!(
+            params.get("fields"),
+            Some(&"id%2Cversion%2Cstatus".to_string())
+        );
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"releases":[{"id":"rel-1","version":"1.2.0","status":"published"}],"limit":50,"offset":0}"#
+                .to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let response = client
+        .list_releases_sparse(ReleaseListQuery::default(), &["id", "version", "status"])
+        .unwrap();
+
+    let release = &response.releases[0];
+    assert_eq!(release.id.as_ref().map(|id| id.as_str()), Some("rel-1"));
+    assert_eq!(release.version.as_deref(), Some("1.2.0"));
+    assert_eq!(release.status.as_deref(), Some("published"));
+    assert_eq!(release.product, None);
+    assert_eq!(release.created_at, None);
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn list_releases_changed_since_sends_updated_since() {
+    let (base_url, handle) = spawn_server(move |request| {
+        let (path, params) = parse_query(&request.path);
+        assert_eq!(path, "/v1/releases");
+        assert_eq!(params.get("updated_since"), Some(&"1700000000".to_string()));
+        assert_eq!(params.get("product"), Some(&"demo".to_string()));
+
+        let body = r#"{"releases":[{"id":"rel-1","product":"demo","version":"1.0.0","status":"published","created_at":1690000000,"updated_at":1700000500}],"limit":50,"offset":0}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let query = ReleaseListQuery {
+        product: Some("demo".to_string()),
+        ..Default::default()
+    };
+    let response = client
+        .list_releases_changed_since(1_700_000_000, &query)
+        .unwrap();
+    assert_eq!(response.releases[0].updated_at, Some(1_700_000_500));
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn list_releases_repeats_status_filter() {
+    let (base_url, handle) = spawn_server(move |request| {
+        let (path, _) = parse_query(&request.path);
+        assert_eq!(path, "/v1/releases");
+        let query = request.path.split_once('?').expect("query string").1;
+        let statuses: Vec<&str> = query
+            .split('&')
+            .filter_map(|pair| pair.strip_prefix("status="))
+            .collect();
+        assert_eq!(statuses, vec!["draft", "published"]);
+
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"releases":[],"limit":50,"offset":0}"#.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let query = ReleaseListQuery::default()
+        .with_status("draft")
+        .with_status("published");
+    client.list_releases(&query).unwrap();
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn create_release_returns_api_error() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/releases");
+        assert_eq!(
+            request.headers.get("x-releasy-admin-key"),
+            Some(&"admin-key".to_string())
+        );
+        let body_json: serde_json::Value =
+            serde_json::from_slice(&request.body).expect("json body");
+        assert_eq!(body_json["product"], "demo");
+        assert_eq!(body_json["version"], "1.0.0");
+
+        let body = r#"{"error":{"code":"release_conflict","message":"already exists"}}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 409 Conflict".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = ReleaseCreateRequest {
+        product: "demo".to_string(),
+        version: "1.0.0".to_string(),
+    };
+
+    let error = client.create_release(&request).expect_err("expected error");
+    match error {
+        Error::Api { status, error, .. } => {
+            assert_eq!(status, 409);
+            let detail = error.expect("error body");
+            assert_eq!(detail.error.code, "release_conflict");
+            assert_eq!(detail.error.message, "already exists");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn chunked_json_response_is_read_to_completion() {
+    let (base_url, handle) = spawn_server_sequence(2, move |_request| {
+        let body = r#"{"releases":[{"id":"rel-1","product":"demo","version":"1.0.0","status":"published","created_at":1,"published_at":2},{"id":"rel-2","product":"demo","version":"1.1.0","status":"draft","created_at":3}],"limit":2,"offset":0}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Transfer-Encoding".to_string(), "chunked".to_string()),
+            ],
+            body: body.to_string(),
+        }
+    });
+
+    for strict in [false, true] {
+        let client = Client::builder(base_url.clone(), Auth::ApiKey("test-key".to_string()))
+            .unwrap()
+            .strict_deserialization(strict)
+            .build()
+            .unwrap();
+        let response = client.list_releases(ReleaseListQuery::default()).unwrap();
+        assert_eq!(response.releases.len(), 2);
+        assert_eq!(response.releases[1].version, "1.1.0");
+        assert_eq!(response.limit, 2);
+    }
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn chunked_error_response_is_read_to_completion() {
+    let (base_url, handle) = spawn_server(move |_request| {
+        let body = r#"{"error":{"code":"release_conflict","message":"a release with this version already exists"}}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 409 Conflict".to_string(),
+            headers: vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Transfer-Encoding".to_string(), "chunked".to_string()),
+            ],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = ReleaseCreateRequest {
+        product: "demo".to_string(),
+        version: "1.0.0".to_string(),
+    };
+
+    let error = client.create_release(&request).expect_err("expected error");
+    assert_eq!(error.status(), Some(409));
+    let detail = error.api_error().expect("error body");
+    assert_eq!(
+        detail.error.message,
+        "a release with this version already exists"
+    );
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn problem_json_error_is_normalized() {
+    let body = r#"{"type":"https://gateway.example/problems/invalid-version","title":"Invalid version","status":400,"detail":"version must be semver"}"#;
+    let (base_url, handle) = spawn_server(move |_request| ResponseSpec {
+        status_line: "HTTP/1.1 400 Bad Request".to_string(),
+        headers: vec![(
+            "Content-Type".to_string(),
+            "application/problem+json".to_string(),
+        )],
+        body: body.to_string(),
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = ReleaseCreateRequest {
+        product: "demo".to_string(),
+        version: "one".to_string(),
+    };
+
+    let error = client.create_release(&request).expect_err("expected error");
+    assert_eq!(error.status(), Some(400));
+    let detail = error.api_error().expect("error body");
+    assert_eq!(detail.error.code, "invalid-version");
+    assert_eq!(detail.error.message, "version must be semver");
+    match error {
+        Error::Api { body: raw, .. } => assert_eq!(raw.as_deref(), Some(body)),
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn upload_presigned_artifact_puts_file_body() {
+    let payload = b"releasy-upload-bytes";
+    let path = write_temp_file(payload);
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "PUT");
+        assert_eq!(request.path, "/upload");
+        assert_eq!(
+            request.headers.get("content-length"),
+            Some(&payload.len().to_string())
+        );
+        assert_eq!(request.body, payload);
+
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![],
+            body: "".to_string(),
+        }
+    });
+
+    let client = Client::new(base_url.clone(), Auth::None).unwrap();
+    let upload_url = format!("{}/upload", base_url);
+    client
+        .upload_presigned_artifact(&upload_url, &path)
+        .expect("upload");
+
+    handle.join().expect("server join");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn upload_presigned_artifact_sends_presign_content_type() {
+    let path = write_temp_file(b"releasy-upload-bytes");
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "PUT");
+        assert_eq!(request.path, "/upload");
+        assert_eq!(
+            request.headers.get("content-type"),
+            Some(&"application/x-custom".to_string())
+        );
+
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![],
+            body: "".to_string(),
+        }
+    });
+
+    let client = Client::new(base_url.clone(), Auth::None).unwrap();
+    let upload_url = format!("{}/upload", base_url);
+    client
+        .upload_presigned_artifact_with_content_type(
+            &upload_url,
+            &path,
+            Some("application/x-custom"),
+        )
+        .expect("upload");
+
+    handle.join().expect("server join");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn upload_presigned_artifact_guesses_content_type_from_extension() {
+    let source = write_temp_file(b"releasy-upload-bytes");
+    let path = source.with_extension("zip");
+    std::fs::rename(&source, &path).expect("rename temp file");
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "PUT");
+        assert_eq!(
+            request.headers.get("content-type"),
+            Some(&"application/zip".to_string())
+        );
+
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![],
+            body: "".to_string(),
+        }
+    });
+
+    let client = Client::new(base_url.clone(), Auth::None).unwrap();
+    let upload_url = format!("{}/upload", base_url);
+    client
+        .upload_presigned_artifact(&upload_url, &path)
+        .expect("upload");
+
+    handle.join().expect("server join");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn upload_artifact_multipart_streams_form_fields() {
+    let payload = b"releasy-multipart-bytes";
+    let path = write_temp_file(payload);
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/releases/rel-1/artifacts/upload");
+        let content_type = request.headers.get("content-type").expect("content type");
+        let boundary = content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .expect("multipart boundary");
+        assert_eq!(
+            request.headers.get("content-length"),
+            Some(&request.body.len().to_string())
+        );
+
+        let body = String::from_utf8(request.body).expect("utf8 body");
+        assert!(body.starts_with(&format!("--{boundary}\r\n")));
+        assert!(body.ends_with(&format!("\r\n--{boundary}--\r\n")));
+        assert!(body.contains("name=\"platform\"\r\n\r\nlinux\r\n"));
+        assert!(body.contains(
+            "name=\"checksum\"\r\n\r\n\
+             6adde2078eea435186a20206092473cb886403577ae2a215a637c869373f109e\r\n"
+        ));
+        assert!(body.contains("name=\"file\"; filename=\""));
+        assert!(body.contains("releasy-multipart-bytes"));
+
+        ResponseSpec {
+            status_line: "HTTP/1.1 201 Created".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"id":"art-1","release_id":"rel-1","object_key":"releases/rel-1/a.bin","checksum":"abc","size":23,"platform":"linux","created_at":1}"#.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::None).unwrap();
+    let response = client
+        .upload_artifact_multipart("rel-1", &path, &Platform::Linux)
+        .expect("upload");
+    assert_eq!(response.id, "art-1");
+    assert_eq!(response.platform, Platform::Linux);
+
+    handle.join().expect("server join");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn upload_artifact_multipart_escapes_control_characters_in_filename() {
+    let source = write_temp_file(b"releasy-multipart-bytes");
+    let path = source.with_file_name(format!(
+        "{}-a\"b\r\nX-Injected: 1\tc.bin",
+        source.file_stem().unwrap().to_str().unwrap()
+    ));
+    std::fs::rename(&source, &path).expect("rename temp file");
+    let (base_url, handle) = spawn_server(move |request| {
+        let body = String::from_utf8(request.body).expect("utf8 body");
+        assert!(
+            body.contains("-a%22b%0D%0AX-Injected: 1%09c.bin\"\r\n"),
+            "{body}"
+        );
+        assert!(!body.contains("\r\nX-Injected"));
+
+        ResponseSpec {
+            status_line: "HTTP/1.1 201 Created".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"id":"art-1","release_id":"rel-1","object_key":"releases/rel-1/a.bin","checksum":"abc","size":23,"platform":"linux","created_at":1}"#.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::None).unwrap();
+    client
+        .upload_artifact_multipart("rel-1", &path, &Platform::Linux)
+        .expect("upload");
+
+    handle.join().expect("server join");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn upload_presigned_artifact_with_length_sends_exact_content_length() {
+    let payload = b"releasy-upload-bytes";
+    let path = write_temp_file(payload);
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "PUT");
+        assert_eq!(
+            request.headers.get("content-length"),
+            Some(&payload.len().to_string())
+        );
+        assert_eq!(request.body, payload);
+
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![],
+            body: "".to_string(),
+        }
+    });
+
+    let client = Client::new(base_url.clone(), Auth::None).unwrap();
+    let upload_url = format!("{}/upload", base_url);
+    client
+        .upload_presigned_artifact_with_length(&upload_url, &path, None, payload.len() as u64)
+        .expect("upload");
+
+    handle.join().expect("server join");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn upload_presigned_artifact_with_length_rejects_size_drift() {
+    let path = write_temp_file(b"releasy-upload-bytes");
+    let client = Client::new("http://localhost", Auth::None).unwrap();
+
+    let error = client
+        .upload_presigned_artifact_with_length("http://localhost:1/upload", &path, None, 5)
+        .expect_err("expected length mismatch");
+    match error {
+        Error::Validation { message } => {
+            assert!(message.contains("20 bytes"), "{message}");
+            assert!(message.contains("Content-Length 5"), "{message}");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn upload_to_presign_uses_presign_url_and_headers() {
+    let payload = b"releasy-upload-bytes";
+    let path = write_temp_file(payload);
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "PUT");
+        assert_eq!(request.path, "/upload?sig=abc");
+        assert_eq!(
+            request.headers.get("content-type"),
+            Some(&"application/gzip".to_string())
+        );
+        assert_eq!(
+            request.headers.get("x-amz-meta-checksum"),
+            Some(&"sha256:1234".to_string())
+        );
+        assert_eq!(request.body, payload);
+
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![],
+            body: "".to_string(),
+        }
+    });
+
+    let client = Client::builder(base_url.clone(), Auth::None)
+        .unwrap()
+        .clock(ManualClock::new(
+            UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+        ))
+        .build()
+        .unwrap();
+    let presign = ArtifactPresignResponse {
+        artifact_id: "art-1".into(),
+        object_key: "releases/rel-1/demo.tar.gz".to_string(),
+        upload_url: format!("{}/upload?sig=abc", base_url),
+        expires_at: 1_700_000_600,
+        content_type: Some("application/gzip".to_string()),
+        required_headers: [("x-amz-meta-checksum".to_string(), "sha256:1234".to_string())]
+            .into_iter()
+            .collect(),
+    };
+    client.upload_to_presign(&presign, &path).expect("upload");
+
+    handle.join().expect("server join");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn upload_to_presign_rejects_expired_presign() {
+    let path = write_temp_file(b"releasy-upload-bytes");
+    let client = Client::builder("http://localhost", Auth::None)
+        .unwrap()
+        .clock(ManualClock::new(
+            UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+        ))
+        .build()
+        .unwrap();
+    let presign = ArtifactPresignResponse {
+        artifact_id: "art-1".into(),
+        object_key: "releases/rel-1/demo.tar.gz".to_string(),
+        upload_url: "http://localhost:1/upload".to_string(),
+        expires_at: 1_699_999_000,
+        content_type: None,
+        required_headers: Default::default(),
+    };
+
+    let error = client
+        .upload_to_presign(&presign, &path)
+        .expect_err("expected expiry error");
+    match error {
+        Error::Validation { message } => assert!(message.contains("expired"), "{message}"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    let _ = std::fs::remove_file(path);
+}
+
+/// Reader that stops after `limit` bytes, like a file truncated mid-upload.
+struct ShortReader {
+    data: std::io::Cursor<Vec<u8>>,
+    limit: usize,
+}
+
+impl Read for ShortReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = self.limit - self.data.position() as usize;
+        let len = buf.len().min(remaining);
+        self.data.read(&mut buf[..len])
+    }
+}
+
+#[test]
+fn upload_presigned_reader_rejects_short_body() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let addr = listener.local_addr().expect("local addr");
+    let handle = thread::spawn(move || {
+        // The client aborts mid-body; just drain until it disconnects.
+        let (mut stream, _) = listener.accept().expect("accept");
+        let mut received = Vec::new();
+        let _ = stream.read_to_end(&mut received);
+        received
+    });
+
+    let client = Client::new(format!("http://{}", addr), Auth::None).unwrap();
+    let reader = ShortReader {
+        data: std::io::Cursor::new(vec![b'x'; 20]),
+        limit: 10,
+    };
+    let error = client
+        .upload_presigned_reader(
+            &format!("http://{}/upload", addr),
+            reader,
+            20,
+            "application/octet-stream",
+        )
+        .expect_err("expected size mismatch");
+
+    match error {
+        Error::SizeMismatch { declared, actual } => {
+            assert_eq!(declared, 20);
+            assert_eq!(actual, 10);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    let received = handle.join().expect("server join");
+    let text = String::from_utf8_lossy(&received);
+    assert!(text.contains("content-length: 20"), "{text}");
+}
+
+#[test]
+fn upload_presigned_reader_streams_exact_length() {
+    let (base_url, handle) = spawn_server(|request| {
+        assert_eq!(request.method, "PUT");
+        assert_eq!(request.body, b"0123456789");
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![],
+            body: "".to_string(),
+        }
+    });
+
+    let client = Client::new(base_url.clone(), Auth::None).unwrap();
+    client
+        .upload_presigned_reader(
+            &format!("{}/upload", base_url),
+            std::io::Cursor::new(b"0123456789".to_vec()),
+            10,
+            "text/plain",
+        )
+        .expect("upload");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn upload_presigned_artifact_missing_file_returns_transport_error() {
+    let client = Client::new("http://localhost", Auth::None).unwrap();
+    let missing_path = std::env::temp_dir().join("releasy-client-missing-upload.bin");
+    let _ = std::fs::remove_file(&missing_path);
+
+    let error = client
+        .upload_presigned_artifact("http://localhost/upload", &missing_path)
+        .expect_err("expected error");
+    match error {
+        Error::Transport {
+            method,
+            url,
+            source: ureq::Error::Io(_),
+        } => {
+            assert_eq!(method, "PUT");
+            assert_eq!(url, "http://localhost/upload");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn resolve_overrides_match_port_and_cap_addresses() {
+    let (base_url, handle) = spawn_server(move |_request| ResponseSpec {
+        status_line: "HTTP/1.1 200 OK".to_string(),
+        headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+        body: r#"{"status":"ok"}"#.to_string(),
+    });
+    let addr: std::net::SocketAddr = base_url
+        .trim_start_matches("http://")
+        .parse()
+        .expect("socket addr");
+    let pinned = format!("http://releasy.invalid:{}", addr.port());
+
+    // More overrides than ureq has room for must not panic.
+    let builder = (0..20).fold(
+        Client::builder(pinned.clone(), Auth::None).unwrap(),
+        |builder, _| builder.resolve("releasy.invalid", addr),
+    );
+    let client = builder.build().unwrap();
+    assert_eq!(client.health_check().unwrap().status, "ok");
+    handle.join().expect("server join");
+
+    // An override for another port leaves this one to DNS.
+    let other = std::net::SocketAddr::new(addr.ip(), addr.port().wrapping_add(1));
+    let client = Client::builder(pinned, Auth::None)
+        .unwrap()
+        .resolve("releasy.invalid", other)
+        .retry_policy(RetryPolicy::new(0))
+        .build()
+        .unwrap();
+    let error = client
+        .health_check()
+        .expect_err("no override for this port");
+    assert!(matches!(error, Error::Dns { .. }), "{error:?}");
+}
+
+#[test]
+fn resolve_override_routes_hostname_to_pinned_address() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/health");
+        let host = request.headers.get("host").expect("host header");
+        assert!(host.starts_with("releasy.invalid:"));
+
+        let body = r#"{"status":"ok"}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let addr: std::net::SocketAddr = base_url
+        .trim_start_matches("http://")
+        .parse()
+        .expect("socket addr");
+    let client = Client::builder(
+        format!("http://releasy.invalid:{}", addr.port()),
+        Auth::None,
+    )
+    .unwrap()
+    .resolve("releasy.invalid", addr)
+    .build()
+    .unwrap();
+    let response = client.health_check().unwrap();
+    assert_eq!(response.status, "ok");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn create_user_failure_captures_redacted_request_body() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/admin/users");
+
+        let body = r#"{"error":{"code":"invalid_request","message":"bad email"}}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 422 Unprocessable Entity".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::builder(base_url, Auth::AdminKey("admin-key".to_string()))
+        .unwrap()
+        .capture_request_body_on_error(true)
+        .build()
+        .unwrap();
+    let request = UserCreateRequest {
+        email: "not-an-email".to_string(),
+        customer_id: "cust-1".into(),
+        display_name: None,
+        groups: None,
+        metadata: Some(serde_json::json!({"password": "hunter2", "team": "ops"})),
+        status: None,
+    };
+
+    let error = client.create_user(&request).expect_err("expected error");
+    assert_eq!(error.status(), Some(422));
+    let captured: serde_json::Value =
+        serde_json::from_str(error.request_body().expect("request body")).expect("json");
+    assert_eq!(captured["email"], "not-an-email");
+    assert_eq!(captured["customer_id"], "cust-1");
+    assert_eq!(captured["metadata"]["team"], "ops");
+    assert_eq!(captured["metadata"]["password"], "[redacted]");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn request_body_is_not_captured_by_default() {
+    let (base_url, handle) = spawn_server(move |_request| {
+        let body = r#"{"error":{"code":"release_conflict","message":"already exists"}}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 409 Conflict".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = ReleaseCreateRequest {
+        product: "demo".to_string(),
+        version: "1.0.0".to_string(),
+    };
+
+    let error = client.create_release(&request).expect_err("expected error");
+    assert!(error.request_body().is_none());
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn download_artifact_bytes_chains_token_resolve_and_fetch() {
+    let (base_url, handle) = spawn_server_sequence(3, move |request| match request.path.as_str() {
+        "/v1/downloads/token" => {
+            assert_eq!(request.method, "POST");
+            assert_eq!(
+                request.headers.get("x-releasy-api-key"),
+                Some(&"test-key".to_string())
+            );
+            let body_json: serde_json::Value =
+                serde_json::from_slice(&request.body).expect("json body");
+            assert_eq!(body_json["artifact_id"], "art-1");
+
+            let body = r#"{"download_url":"/v1/downloads/tok-1","expires_at":1700000000}"#;
+            ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body: body.to_string(),
+            }
+        }
+        "/v1/downloads/tok-1" => {
+            assert_eq!(request.method, "GET");
+            let host = request.headers.get("host").expect("host header");
+            ResponseSpec {
+                status_line: "HTTP/1.1 302 Found".to_string(),
+                headers: vec![(
+                    "Location".to_string(),
+                    format!("http://{}/files/artifact.bin", host),
+                )],
+                body: "".to_string(),
+            }
+        }
+        "/files/artifact.bin" => {
+            assert_eq!(request.method, "GET");
+            assert!(!request.headers.contains_key("x-releasy-api-key"));
+            ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![(
+                    "Content-Type".to_string(),
+                    "application/octet-stream".to_string(),
+                )],
+                body: "artifact-bytes".to_string(),
+            }
+        }
+        other => panic!("unexpected path: {other}"),
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let request = DownloadTokenRequest {
+        artifact_id: "art-1".into(),
+        expires_in_seconds: None,
+        purpose: None,
+    };
+
+    let bytes = client.download_artifact_bytes(&request).unwrap();
+    assert_eq!(bytes, b"artifact-bytes");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn download_artifact_reports_failed_stage() {
+    let (base_url, handle) = spawn_server_sequence(2, move |request| match request.path.as_str() {
+        "/v1/downloads/token" => {
+            let body = r#"{"download_url":"/v1/downloads/tok-2","expires_at":1700000000}"#;
+            ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body: body.to_string(),
+            }
+        }
+        "/v1/downloads/tok-2" => {
+            let body = r#"{"error":{"code":"not_found","message":"token expired"}}"#;
+            ResponseSpec {
+                status_line: "HTTP/1.1 404 Not Found".to_string(),
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body: body.to_string(),
+            }
+        }
+        other => panic!("unexpected path: {other}"),
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let request = DownloadTokenRequest {
+        artifact_id: "art-2".into(),
+        expires_in_seconds: None,
+        purpose: None,
+    };
+
+    let error = client
+        .download_artifact_bytes(&request)
+        .expect_err("expected error");
+    assert_eq!(error.download_stage(), Some(DownloadStage::Resolve));
+    assert_eq!(error.status(), Some(404));
+    assert_eq!(error.api_error().unwrap().error.code, "not_found");
+
+    handle.join().expect("server join");
+}
+
+fn verified_download_server(checksum: &str) -> (String, thread::JoinHandle<()>) {
+    let checksum = checksum.to_string();
+    spawn_server_sequence(4, move |request| {
+        let (path, params) = parse_query(&request.path);
+        match path.as_str() {
+            "/v1/releases" => {
+                assert_eq!(params.get("include_artifacts"), Some(&"true".to_string()));
+                let body = format!(
+                    r#"{{"releases":[{{"id":"rel-1","product":"demo","version":"1.0.0","status":"published","created_at":1,"artifacts":[{{"id":"art-1","object_key":"releases/rel-1/a.bin","platform":"linux","checksum":"{checksum}","size":14}}]}}],"limit":200,"offset":0}}"#
+                );
+                ResponseSpec {
+                    status_line: "HTTP/1.1 200 OK".to_string(),
+                    headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                    body,
+                }
+            }
+            "/v1/downloads/token" => ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body: r#"{"download_url":"/v1/downloads/tok-1","expires_at":1700000000}"#
+                    .to_string(),
+            },
+            "/v1/downloads/tok-1" => {
+                let host = request.headers.get("host").expect("host header");
+                ResponseSpec {
+                    status_line: "HTTP/1.1 302 Found".to_string(),
+                    headers: vec![(
+                        "Location".to_string(),
+                        format!("http://{}/files/artifact.bin", host),
+                    )],
+                    body: "".to_string(),
+                }
+            }
+            "/files/artifact.bin" => ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![],
+                body: "artifact-bytes".to_string(),
+            },
+            other => panic!("unexpected path: {other}"),
+        }
+    })
+}
+
+#[test]
+fn download_verified_accepts_matching_checksum() {
+    let (base_url, handle) = verified_download_server(
+        "sha256:6521df166eb07efaf36eba5b6bedefd9d6a252e9c80bab1c99653700ec71473c",
+    );
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let mut bytes = Vec::new();
+    client
+        .download_verified("rel-1", "art-1", &mut bytes)
+        .expect("verified download");
+    assert_eq!(bytes, b"artifact-bytes");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn download_verified_hashes_md5_checksums_with_md5() {
+    for checksum in [
+        "md5:3221E9231E9696CED6B18EEBE25EDB8E",
+        "3221e9231e9696ced6b18eebe25edb8e",
+    ] {
+        let (base_url, handle) = verified_download_server(checksum);
+
+        let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+        let mut bytes = Vec::new();
+        client
+            .download_verified("rel-1", "art-1", &mut bytes)
+            .unwrap_or_else(|err| panic!("{checksum}: {err}"));
+        assert_eq!(bytes, b"artifact-bytes");
+
+        handle.join().expect("server join");
+    }
+}
+
+#[test]
+fn download_verified_rejects_unsupported_checksums_before_downloading() {
+    let (base_url, handle) = spawn_server(|request| {
+        assert_eq!(parse_query(&request.path).0, "/v1/releases");
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"releases":[{"id":"rel-1","product":"demo","version":"1.0.0","status":"published","created_at":1,"artifacts":[{"id":"art-1","object_key":"k","platform":"linux","checksum":"crc32:0000","size":14}]}],"limit":100,"offset":0}"#.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let error = client
+        .download_verified("rel-1", "art-1", &mut Vec::new())
+        .expect_err("unsupported checksum");
+    match error {
+        Error::UnsupportedChecksum { checksum } => assert_eq!(checksum, "crc32:0000"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn download_verified_finds_releases_past_a_server_clamped_limit() {
+    let (base_url, handle) = spawn_server_sequence(5, move |request| {
+        let (path, params) = parse_query(&request.path);
+        match path.as_str() {
+            "/v1/releases" => {
+                let (offset, id) = match params.get("offset").map(String::as_str) {
+                    Some("0") => (0, "rel-0"),
+                    Some("1") => (1, "rel-1"),
+                    other => panic!("unexpected offset: {other:?}"),
+                };
+                ResponseSpec {
+                    status_line: "HTTP/1.1 200 OK".to_string(),
+                    headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                    body: format!(
+                        r#"{{"releases":[{{"id":"{id}","product":"demo","version":"1.0.0","status":"published","created_at":1,"artifacts":[{{"id":"art-1","object_key":"k","platform":"linux","checksum":"3221e9231e9696ced6b18eebe25edb8e","size":14}}]}}],"limit":1,"offset":{offset}}}"#
+                    ),
+                }
+            }
+            "/v1/downloads/token" => ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body: r#"{"download_url":"/v1/downloads/tok-1","expires_at":1700000000}"#
+                    .to_string(),
+            },
+            "/v1/downloads/tok-1" => {
+                let host = request.headers.get("host").expect("host header");
+                ResponseSpec {
+                    status_line: "HTTP/1.1 302 Found".to_string(),
+                    headers: vec![(
+                        "Location".to_string(),
+                        format!("http://{}/files/artifact.bin", host),
+                    )],
+                    body: "".to_string(),
+                }
+            }
+            "/files/artifact.bin" => ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![],
+                body: "artifact-bytes".to_string(),
+            },
+            other => panic!("unexpected path: {other}"),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let mut bytes = Vec::new();
+    client
+        .download_verified("rel-1", "art-1", &mut bytes)
+        .expect("verified download");
+    assert_eq!(bytes, b"artifact-bytes");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn download_verified_rejects_checksum_mismatch() {
+    let expected = format!("sha256:{}", "0".repeat(64));
+    let (base_url, handle) = verified_download_server(&expected);
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let mut bytes = Vec::new();
+    let error = client
+        .download_verified("rel-1", "art-1", &mut bytes)
+        .expect_err("expected mismatch");
+    match error {
+        Error::ChecksumMismatch {
+            expected: reported,
+            actual,
+        } => {
+            assert_eq!(reported, expected);
+            assert_eq!(
+                actual,
+                "6521df166eb07efaf36eba5b6bedefd9d6a252e9c80bab1c99653700ec71473c"
+            );
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    handle.join().expect("server join");
+}
+
+fn latest_release_list_body() -> String {
+    let artifact = |id: &str, platform: &str| {
+        format!(
+            r#"{{"id":"{id}","object_key":"k/{id}","platform":"{platform}","checksum":"abc","size":14}}"#
+        )
+    };
+    let release = |id: &str, version: &str, published_at: i64, artifacts: String| {
+        format!(
+            r#"{{"id":"{id}","product":"demo","version":"{version}","status":"published","created_at":1,"published_at":{published_at},"artifacts":[{artifacts}]}}"#
+        )
+    };
+    let releases = [
+        release("rel-1", "1.0.0", 100, artifact("art-1", "linux")),
+        release(
+            "rel-3",
+            "1.2.0",
+            300,
+            format!(
+                "{},{}",
+                artifact("art-3w", "windows"),
+                artifact("art-3", "linux")
+            ),
+        ),
+        release("rel-4", "1.3.0", 400, artifact("art-4", "windows")),
+        release("rel-2", "1.1.0", 200, artifact("art-2", "linux")),
+    ];
+    format!(
+        r#"{{"releases":[{}],"limit":200,"offset":0}}"#,
+        releases.join(",")
+    )
+}
+
+#[test]
+fn download_latest_fetches_newest_matching_artifact() {
+    let (base_url, handle) = spawn_server_sequence(4, move |request| {
+        let (path, params) = parse_query(&request.path);
+        match path.as_str() {
+            "/v1/releases" => {
+                assert_eq!(params.get("product"), Some(&"demo".to_string()));
+                assert_eq!(params.get("status"), Some(&"published".to_string()));
+                assert_eq!(params.get("include_artifacts"), Some(&"true".to_string()));
+                assert_eq!(params.get("platform"), Some(&"linux".to_string()));
+                ResponseSpec {
+                    status_line: "HTTP/1.1 200 OK".to_string(),
+                    headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                    body: latest_release_list_body(),
+                }
+            }
+            "/v1/downloads/token" => {
+                let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+                assert_eq!(body["artifact_id"], "art-3");
+                ResponseSpec {
+                    status_line: "HTTP/1.1 200 OK".to_string(),
+                    headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                    body: r#"{"download_url":"/v1/downloads/tok-1","expires_at":1700000000}"#
+                        .to_string(),
+                }
+            }
+            "/v1/downloads/tok-1" => {
+                let host = request.headers.get("host").expect("host header");
+                ResponseSpec {
+                    status_line: "HTTP/1.1 302 Found".to_string(),
+                    headers: vec![(
+                        "Location".to_string(),
+                        format!("http://{}/files/artifact.bin", host),
+                    )],
+                    body: "".to_string(),
+                }
+            }
+            "/files/artifact.bin" => ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![],
+                body: "artifact-bytes".to_string(),
+            },
+            other => panic!("unexpected path: {other}"),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let mut bytes = Vec::new();
+    let release = client
+        .download_latest("demo", "linux", &mut bytes)
+        .expect("latest download");
+    assert_eq!(release.id, "rel-3");
+    assert_eq!(release.version, "1.2.0");
+    assert_eq!(bytes, b"artifact-bytes");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn download_latest_reports_missing_platform() {
+    let (base_url, handle) = spawn_server(move |_request| ResponseSpec {
+        status_line: "HTTP/1.1 200 OK".to_string(),
+        headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+        body: latest_release_list_body(),
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let mut bytes = Vec::new();
+    let error = client
+        .download_latest("demo", "macos", &mut bytes)
+        .expect_err("no macos artifact");
+    match error {
+        Error::NoMatchingArtifact { product, platform } => {
+            assert_eq!(product, "demo");
+            assert_eq!(platform, "macos");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert!(bytes.is_empty());
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn download_latest_pages_past_a_server_clamped_limit() {
+    let release = |id: &str, published_at: i64, platform: &str| {
+        format!(
+            r#"{{"id":"{id}","product":"demo","version":"1.0.0","status":"published","created_at":1,"published_at":{published_at},"artifacts":[{{"id":"art-{id}","object_key":"k","platform":"{platform}","checksum":"abc","size":14}}]}}"#
+        )
+    };
+    let (base_url, handle) = spawn_server_sequence(6, move |request| {
+        let (path, params) = parse_query(&request.path);
+        match path.as_str() {
+            "/v1/releases" => {
+                assert_eq!(params.get("limit"), Some(&"100".to_string()));
+                let (offset, releases) = match params.get("offset").map(String::as_str) {
+                    Some("0") => (0, release("rel-1", 100, "windows")),
+                    Some("1") => (1, release("rel-2", 200, "linux")),
+                    Some("2") => (2, String::new()),
+                    other => panic!("unexpected offset: {other:?}"),
+                };
+                ResponseSpec {
+                    status_line: "HTTP/1.1 200 OK".to_string(),
+                    headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                    body: format!(r#"{{"releases":[{releases}],"limit":1,"offset":{offset}}}"#),
+                }
+            }
+            "/v1/downloads/token" => ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body: r#"{"download_url":"/v1/downloads/tok-1","expires_at":1700000000}"#
+                    .to_string(),
+            },
+            "/v1/downloads/tok-1" => {
+                let host = request.headers.get("host").expect("host header");
+                ResponseSpec {
+                    status_line: "HTTP/1.1 302 Found".to_string(),
+                    headers: vec![(
+                        "Location".to_string(),
+                        format!("http://{}/files/artifact.bin", host),
+                    )],
+                    body: "".to_string(),
+                }
+            }
+            "/files/artifact.bin" => ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![],
+                body: "artifact-bytes".to_string(),
+            },
+            other => panic!("unexpected path: {other}"),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let mut bytes = Vec::new();
+    let release = client
+        .download_latest("demo", "linux", &mut bytes)
+        .expect("download latest");
+    assert_eq!(release.id, "rel-2");
+    assert_eq!(bytes, b"artifact-bytes");
+
+    handle.join().expect("server join");
+}
+
+fn download_redirect_server(
+    status_line: &'static str,
+    payload: String,
+) -> (String, thread::JoinHandle<()>) {
+    spawn_server_sequence(2, move |request| match request.path.as_str() {
+        "/v1/downloads/tok-1" => {
+            let host = request.headers.get("host").expect("host header");
+            ResponseSpec {
+                status_line: "HTTP/1.1 302 Found".to_string(),
+                headers: vec![(
+                    "Location".to_string(),
+                    format!("http://{}/files/artifact.bin", host),
+                )],
+                body: "".to_string(),
+            }
+        }
+        "/files/artifact.bin" => ResponseSpec {
+            status_line: status_line.to_string(),
+            headers: vec![],
+            body: payload.clone(),
+        },
+        other => panic!("unexpected path: {other}"),
+    })
+}
+
+#[test]
+fn download_reader_streams_artifact_body() {
+    let payload = "releasy-stream-".repeat(4096);
+    let (base_url, handle) = download_redirect_server("HTTP/1.1 200 OK", payload.clone());
+
+    let client = Client::new(base_url, Auth::None).unwrap();
+    let mut reader = client.download_reader("tok-1").expect("reader");
+    let mut streamed = String::new();
+    reader.read_to_string(&mut streamed).expect("read body");
+    assert_eq!(streamed, payload);
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn download_reader_fails_before_reading_on_error_status() {
+    let (base_url, handle) =
+        download_redirect_server("HTTP/1.1 403 Forbidden", "expired".to_string());
+
+    let client = Client::new(base_url, Auth::None).unwrap();
+    let error = match client.download_reader("tok-1") {
+        Ok(_) => panic!("expected error"),
+        Err(error) => error,
+    };
+    assert_eq!(error.download_stage(), Some(DownloadStage::Fetch));
+    assert_eq!(error.status(), Some(403));
+    assert_eq!(error.body(), Some("expired"));
+
+    handle.join().expect("server join");
+}
+
+/// Writer that cancels its token after the first chunk it receives.
+struct CancelAfterFirstWrite {
+    token: CancellationToken,
+    bytes: Vec<u8>,
+}
+
+impl Write for CancelAfterFirstWrite {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.bytes.extend_from_slice(buf);
+        self.token.cancel();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn download_with_cancel_stops_mid_transfer() {
+    let payload = "releasy-cancel-".repeat(4096);
+    let payload_len = payload.len();
+    let (base_url, handle) = spawn_server_sequence(3, move |request| match request.path.as_str() {
+        "/v1/downloads/token" => ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"download_url":"/v1/downloads/tok-1","expires_at":1700000000}"#.to_string(),
+        },
+        "/v1/downloads/tok-1" => {
+            let host = request.headers.get("host").expect("host header");
+            ResponseSpec {
+                status_line: "HTTP/1.1 302 Found".to_string(),
+                headers: vec![(
+                    "Location".to_string(),
+                    format!("http://{}/files/artifact.bin", host),
+                )],
+                body: "".to_string(),
+            }
+        }
+        "/files/artifact.bin" => ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![],
+            body: payload.clone(),
+        },
+        other => panic!("unexpected path: {other}"),
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let token = CancellationToken::new();
+    let mut writer = CancelAfterFirstWrite {
+        token: token.clone(),
+        bytes: Vec::new(),
+    };
+    let request = DownloadTokenRequest {
+        artifact_id: "art-1".into(),
+        expires_in_seconds: None,
+        purpose: None,
+    };
+
+    let error = client
+        .download_artifact_to_writer_with_cancel(&request, &mut writer, &token)
+        .expect_err("expected cancellation");
+    assert!(
+        matches!(error, Error::Cancelled),
+        "unexpected error: {error:?}"
+    );
+    assert!(!writer.bytes.is_empty());
+    assert!(writer.bytes.len() < payload_len);
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn upload_with_cancelled_token_returns_before_sending() {
+    let path = write_temp_file(b"releasy-upload-bytes");
+    let client = Client::new("http://localhost", Auth::None).unwrap();
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let error = client
+        .upload_presigned_artifact_with_cancel("http://localhost:1/upload", &path, None, &token)
+        .expect_err("expected cancellation");
+    assert!(
+        matches!(error, Error::Cancelled),
+        "unexpected error: {error:?}"
+    );
+    assert_eq!(client.stats().requests_total, 0);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn slow_server_surfaces_timeout_error() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let addr = listener.local_addr().expect("local addr");
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("accept");
+        thread::sleep(Duration::from_millis(500));
+        drop(stream);
+    });
+
+    let client = Client::builder(format!("http://{}", addr), Auth::None)
+        .unwrap()
+        .timeout_global(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    let error = client.health_check().expect_err("expected timeout");
+    let source = std::error::Error::source(&error).expect("timeout source");
+    assert!(matches!(
+        source.downcast_ref::<ureq::Error>(),
+        Some(ureq::Error::Timeout(_))
+    ));
+    match error {
+        Error::Timeout { method, url, .. } => {
+            assert_eq!(method, "GET");
+            assert_eq!(url, format!("http://{}/health", addr));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn list_entitlements_sends_active_filters() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "GET");
+        let (path, params) = parse_query(&request.path);
+        assert_eq!(path, "/v1/admin/customers/cust-1/entitlements");
+        assert_eq!(params.get("product"), Some(&"demo".to_string()));
+        assert_eq!(params.get("active_at"), Some(&"1700000000".to_string()));
+        assert_eq!(params.get("include_expired"), Some(&"false".to_string()));
+        assert!(!params.contains_key("limit"));
+        assert!(!params.contains_key("offset"));
+
+        let body = r#"{"entitlements":[{"id":"ent-1","customer_id":"cust-1","product":"demo","starts_at":1690000000}],"limit":50,"offset":0}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let query = EntitlementListQuery {
+        product: Some("demo".to_string()),
+        active_at: Some(1_700_000_000),
+        include_expired: Some(false),
+        ..Default::default()
+    };
+
+    let response = client.list_entitlements("cust-1", &query).unwrap();
+    assert_eq!(response.entitlements.len(), 1);
+    assert_eq!(response.entitlements[0].id, "ent-1");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn list_all_entitlements_uses_global_path_and_filters() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "GET");
+        let (path, params) = parse_query(&request.path);
+        assert_eq!(path, "/v1/admin/entitlements");
+        assert_eq!(params.get("product"), Some(&"demo".to_string()));
+        assert_eq!(params.get("customer_id"), Some(&"cust-2".to_string()));
+        assert_eq!(params.get("limit"), Some(&"20".to_string()));
+        assert_eq!(params.get("offset"), Some(&"40".to_string()));
+        assert!(!params.contains_key("active_at"));
+
+        let body = r#"{"entitlements":[{"id":"ent-1","customer_id":"cust-2","product":"demo","starts_at":1690000000}],"limit":20,"offset":40}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let response = client
+        .list_all_entitlements(GlobalEntitlementListQuery {
+            product: Some("demo".to_string()),
+            customer_id: Some("cust-2".into()),
+            limit: Some(20),
+            offset: Some(40),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(response.entitlements.len(), 1);
+    assert_eq!(response.entitlements[0].customer_id, "cust-2");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn customer_scope_creates_entitlement_for_bound_customer() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/admin/customers/cust-1/entitlements");
+        assert_eq!(
+            request.headers.get("x-releasy-admin-key"),
+            Some(&"admin-key".to_string())
+        );
+        let body_json: serde_json::Value =
+            serde_json::from_slice(&request.body).expect("json body");
+        assert_eq!(body_json["product"], "demo");
+        assert_eq!(body_json["starts_at"], 1_700_000_000);
+
+        let body =
+            r#"{"id":"ent-1","customer_id":"cust-1","product":"demo","starts_at":1700000000}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let customer = client.for_customer("cust-1");
+    assert_eq!(customer.customer_id(), "cust-1");
+    let request = EntitlementCreateRequest {
+        product: "demo".to_string(),
+        starts_at: 1_700_000_000,
+        ends_at: None,
+        metadata: None,
+    };
+
+    let response = customer.create_entitlement(&request).unwrap();
+    assert_eq!(response.id, "ent-1");
+    assert_eq!(response.customer_id, "cust-1");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn create_entitlements_bulk_reports_mixed_results() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/admin/customers/cust-1/entitlements/bulk");
+        let body_json: serde_json::Value =
+            serde_json::from_slice(&request.body).expect("json body");
+        assert_eq!(body_json["entitlements"][0]["product"], "demo");
+        assert_eq!(body_json["entitlements"][1]["product"], "pro-addon");
+
+        let body = r#"{"results":[{"index":0,"product":"demo","entitlement":{"id":"ent-1","customer_id":"cust-1","product":"demo","starts_at":1700000000}},{"index":1,"product":"pro-addon","error":{"code":"unknown_product","message":"product not found"}}]}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = EntitlementBulkCreateRequest {
+        entitlements: vec![
+            EntitlementCreateRequest::perpetual("demo", 1_700_000_000),
+            EntitlementCreateRequest::perpetual("pro-addon", 1_700_000_000),
+        ],
+    };
+    let response = client
+        .for_customer("cust-1")
+        .create_entitlements_bulk(&request)
+        .unwrap();
+
+    assert!(!response.all_created());
+    let created: Vec<_> = response.created().collect();
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0].id, "ent-1");
+    let failures: Vec<_> = response.failures().collect();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].index, 1);
+    assert_eq!(failures[0].product, "pro-addon");
+    assert_eq!(
+        failures[0].error.as_ref().map(|error| error.code.as_str()),
+        Some("unknown_product")
+    );
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn unresolvable_host_is_a_dns_error() {
+    let client = Client::new("http://releasy-client-test.invalid", Auth::None).unwrap();
+    let error = client.health_check().expect_err("expected error");
+    match &error {
+        Error::Dns {
+            method,
+            url,
+            host,
+            source,
+        } => {
+            assert_eq!(method, "GET");
+            assert_eq!(url, "http://releasy-client-test.invalid/health");
+            assert_eq!(host, "releasy-client-test.invalid");
+            assert!(matches!(**source, ureq::Error::HostNotFound));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert!(error.to_string().contains("could not resolve host"));
+    let source = std::error::Error::source(&error).expect("dns source");
+    assert!(matches!(
+        source.downcast_ref::<ureq::Error>(),
+        Some(ureq::Error::HostNotFound)
+    ));
+}
+
+#[test]
+fn transport_error_display_includes_method_and_url() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let addr = listener.local_addr().expect("local addr");
+    drop(listener);
+
+    let client = Client::new(format!("http://{}", addr), Auth::None).unwrap();
+    let request = ReleaseCreateRequest {
+        product: "demo".to_string(),
+        version: "1.0.0".to_string(),
+    };
+    let error = client.create_release(&request).expect_err("expected error");
+    assert!(matches!(error, Error::Transport { .. }));
+    let message = error.to_string();
+    assert!(
+        message.starts_with(&format!(
+            "transport error on POST http://{}/v1/releases: ",
+            addr
+        )),
+        "unexpected message: {message}"
+    );
+}
+
+fn release_with_unknown_field_server() -> (String, thread::JoinHandle<()>) {
+    spawn_server(move |request| {
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/releases");
+
+        let body = r#"{"id":"rel-1","product":"demo","version":"1.0.0","status":"draft","created_at":1700000000,"channel":"beta"}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    })
+}
+
+#[test]
+fn strict_deserialization_rejects_unknown_fields() {
+    let (base_url, handle) = release_with_unknown_field_server();
+
+    let client = Client::builder(base_url, Auth::AdminKey("admin-key".to_string()))
+        .unwrap()
+        .strict_deserialization(true)
+        .build()
+        .unwrap();
+    let request = ReleaseCreateRequest {
+        product: "demo".to_string(),
+        version: "1.0.0".to_string(),
+    };
+
+    let error = client.create_release(&request).expect_err("expected error");
+    match error {
+        Error::Decode { unknown_fields } => assert_eq!(unknown_fields, vec!["channel"]),
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn lenient_deserialization_ignores_unknown_fields() {
+    let (base_url, handle) = release_with_unknown_field_server();
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = ReleaseCreateRequest {
+        product: "demo".to_string(),
+        version: "1.0.0".to_string(),
+    };
+
+    let response = client.create_release(&request).unwrap();
+    assert_eq!(response.id, "rel-1");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn list_release_audit_events_uses_release_path() {
+    let (base_url, handle) = spawn_server(|request| {
+        assert_eq!(request.method, "GET");
+        assert_eq!(
+            request.path,
+            "/v1/releases/rel-1/audit-events?event=release.published&limit=10"
+        );
+        let body = r#"{"events":[{"id":"evt-1","actor":"admin","event":"release.published","created_at":1700000000,"payload":{"release_id":"rel-1"}}],"limit":10,"offset":0}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let query = AuditEventListQuery {
+        event: Some("release.published".to_string()),
+        limit: Some(10),
+        ..Default::default()
+    };
+    let response = client.list_release_audit_events("rel-1", &query).unwrap();
+
+    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events[0].id, "evt-1");
+    assert_eq!(response.events[0].event, "release.published");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn list_release_audit_events_maps_not_found() {
+    let (base_url, handle) = spawn_server(|_request| ResponseSpec {
+        status_line: "HTTP/1.1 404 Not Found".to_string(),
+        headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+        body: r#"{"error":{"code":"not_found","message":"release missing"}}"#.to_string(),
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let error = client
+        .list_release_audit_events("rel-missing", AuditEventListQuery::default())
+        .expect_err("expected error");
+
+    match error {
+        Error::ReleaseNotFound { release_id } => assert_eq!(release_id, "rel-missing"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn create_release_with_location_resolves_relative_location() {
+    let (base_url, handle) = spawn_server(|request| {
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/releases");
+        let body = r#"{"id":"rel-1","product":"demo","version":"1.0.0","status":"draft","created_at":1700000000}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 201 Created".to_string(),
+            headers: vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Location".to_string(), "/v1/releases/rel-1".to_string()),
+            ],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url.clone(), Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = ReleaseCreateRequest {
+        product: "demo".to_string(),
+        version: "1.0.0".to_string(),
+    };
+    let created = client.create_release_with_location(&request).unwrap();
+
+    assert_eq!(created.value.id, "rel-1");
+    assert_eq!(
+        created.location,
+        Some(format!("{}/v1/releases/rel-1", base_url))
+    );
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn create_user_with_location_keeps_absolute_location() {
+    let (base_url, handle) = spawn_server(|request| {
+        assert_eq!(request.path, "/v1/admin/users");
+        let body = r#"{"id":"user-1","keycloak_user_id":"kc-1","customer_id":"cust-2","email":"bob","status":"active","groups":[],"created_at":1700000000,"updated_at":1700000000}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 201 Created".to_string(),
+            headers: vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                (
+                    "Location".to_string(),
+                    "https://api.example.com/v1/admin/users/user-1".to_string(),
+                ),
+            ],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = UserCreateRequest {
+        email: "bob".to_string(),
+        customer_id: "cust-2".into(),
+        display_name: None,
+        groups: None,
+        metadata: None,
+        status: None,
+    };
+    let created = client.create_user_with_location(&request).unwrap();
+
+    assert_eq!(created.value.id, "user-1");
+    assert_eq!(
+        created.location.as_deref(),
+        Some("https://api.example.com/v1/admin/users/user-1")
+    );
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn create_or_get_release_returns_existing_on_conflict() {
+    let mut calls = 0;
+    let (base_url, handle) = spawn_server_sequence(2, move |request| {
+        calls += 1;
+        match calls {
+            1 => {
+                assert_eq!(request.method, "POST");
+                assert_eq!(request.path, "/v1/releases");
+                ResponseSpec {
+                    status_line: "HTTP/1.1 409 Conflict".to_string(),
+                    headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                    body: r#"{"error":{"code":"release_conflict","message":"already exists"}}"#
+                        .to_string(),
+                }
+            }
+            _ => {
+                assert_eq!(request.method, "GET");
+                assert_eq!(request.path, "/v1/releases?product=demo&version=1.0.0");
+                let body = r#"{"releases":[{"id":"rel-7","product":"demo","version":"1.0.0","status":"published","created_at":1700000000}],"limit":50,"offset":0}"#;
+                ResponseSpec {
+                    status_line: "HTTP/1.1 200 OK".to_string(),
+                    headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                    body: body.to_string(),
+                }
+            }
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = ReleaseCreateRequest {
+        product: "demo".to_string(),
+        version: "1.0.0".to_string(),
+    };
+    let release = client.create_or_get_release(&request).unwrap();
+
+    assert_eq!(release.id, "rel-7");
+    assert_eq!(release.status, "published");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn create_release_and_fetch_posts_then_reads_back() {
+    let mut calls = 0;
+    let (base_url, handle) = spawn_server_sequence(2, move |request| {
+        calls += 1;
+        match calls {
+            1 => {
+                assert_eq!(request.method, "POST");
+                assert_eq!(request.path, "/v1/releases");
+                let body = r#"{"id":"rel-1","product":"demo","version":"1.0.0","status":"draft","created_at":1700000000}"#;
+                ResponseSpec {
+                    status_line: "HTTP/1.1 200 OK".to_string(),
+                    headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                    body: body.to_string(),
+                }
+            }
+            _ => {
+                assert_eq!(request.method, "GET");
+                let (path, params) = parse_query(&request.path);
+                assert_eq!(path, "/v1/releases");
+                assert_eq!(params.get("product"), Some(&"demo".to_string()));
+                assert_eq!(params.get("version"), Some(&"1.0.0".to_string()));
+                let body = r#"{"releases":[{"id":"rel-1","product":"demo","version":"1.0.0","status":"draft","created_at":1700000000,"artifacts":[]}],"limit":50,"offset":0}"#;
+                ResponseSpec {
+                    status_line: "HTTP/1.1 200 OK".to_string(),
+                    headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                    body: body.to_string(),
+                }
+            }
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = ReleaseCreateRequest {
+        product: "demo".to_string(),
+        version: "1.0.0".to_string(),
+    };
+
+    let response = client.create_release_and_fetch(&request).unwrap();
+    assert_eq!(response.id, "rel-1");
+    assert_eq!(response.artifacts, Some(vec![]));
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn send_raw_returns_unparsed_response() {
+    let mut attempts = 0;
+    let (base_url, handle) = spawn_server_sequence(2, move |request| {
+        attempts += 1;
+        assert_eq!(
+            request.headers.get("x-releasy-api-key"),
+            Some(&"test-key".to_string())
+        );
+        if attempts == 1 {
+            assert_eq!(request.method, "GET");
+            assert_eq!(request.path, "/v1/releases?limit=1");
+            assert!(request.body.is_empty());
+            ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![
+                    ("Content-Type".to_string(), "application/json".to_string()),
+                    ("X-Request-Id".to_string(), "req-42".to_string()),
+                ],
+                body: r#"{"releases":[],"limit":1,"offset":0}"#.to_string(),
+            }
+        } else {
+            assert_eq!(request.method, "PUT");
+            assert_eq!(request.body, b"raw-bytes");
+            ResponseSpec {
+                status_line: "HTTP/1.1 404 Not Found".to_string(),
+                headers: vec![],
+                body: "missing".to_string(),
+            }
+        }
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let mut response = client
+        .send_raw(Method::GET, "/v1/releases?limit=1", None)
+        .expect("raw get");
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(
+        response
+            .headers()
+            .get("x-request-id")
+            .and_then(|value| value.to_str().ok()),
+        Some("req-42")
+    );
+    let body = response.body_mut().read_to_string().unwrap();
+    assert!(body.contains(r#""releases":[]"#));
+
+    let mut response = client
+        .send_raw(Method::PUT, "/v1/custom", Some(b"raw-bytes"))
+        .expect("error statuses are responses");
+    assert_eq!(response.status().as_u16(), 404);
+    assert_eq!(response.body_mut().read_to_string().unwrap(), "missing");
+
+    let error = client
+        .send_raw(Method::CONNECT, "/v1/custom", None)
+        .expect_err("unsupported method");
+    assert!(matches!(error, Error::Validation { .. }), "{error:?}");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn client_exposes_base_url_and_auth_kind() {
+    let cases = [
+        (Auth::None, "none"),
+        (Auth::AdminKey("admin-secret".to_string()), "admin"),
+        (Auth::ApiKey("api-secret".to_string()), "api"),
+        (Auth::OperatorJwt("jwt-secret".to_string()), "operator"),
+        (
+            Auth::AdminWithOperator {
+                admin_key: "admin-secret".to_string(),
+                operator_jwt: "jwt-secret".to_string(),
+            },
+            "admin_operator",
+        ),
+    ];
+    for (auth, expected) in cases {
+        let client = Client::new("https://releasy.example.com/", auth).unwrap();
+        assert_eq!(client.base_url(), "https://releasy.example.com");
+        assert_eq!(client.auth_kind(), expected);
+    }
+}
+
+#[test]
+fn admin_with_operator_sends_both_headers() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(
+            request.headers.get("x-releasy-admin-key"),
+            Some(&"admin-key".to_string())
+        );
+        assert_eq!(
+            request.headers.get("authorization"),
+            Some(&"Bearer operator-jwt".to_string())
+        );
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"users":[],"limit":10,"offset":0}"#.to_string(),
+        }
+    });
+
+    let auth = Auth::AdminWithOperator {
+        admin_key: "admin-key".to_string(),
+        operator_jwt: "operator-jwt".to_string(),
+    };
+    let client = Client::new(base_url, auth).unwrap();
+    let response = client.list_users(UserListQuery::default()).unwrap();
+    assert!(response.users.is_empty());
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn stats_count_requests_across_clones() {
+    let (base_url, handle) = spawn_server_sequence(3, move |request| {
+        assert_eq!(request.path, "/health");
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"status":"ok"}"#.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::None).unwrap();
+    assert_eq!(client.stats(), ClientStats::default());
+    let clone = client.with_auth(Auth::ApiKey("test-key".to_string()));
+    client.health_check().unwrap();
+    client.health_check().unwrap();
+    clone.health_check().unwrap();
+
+    let stats = client.stats();
+    assert_eq!(stats.requests_total, 3);
+    // The mock server closes every connection, so nothing is reused.
+    assert_eq!(stats.new_connections, 3);
+    assert_eq!(stats.reused_connections(), 0);
+    assert_eq!(clone.stats(), stats);
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn custom_agent_with_agent_level_options_is_a_config_conflict() {
+    let agent: ureq::Agent = ureq::Agent::config_builder().build().into();
+    let error = Client::builder("http://localhost", Auth::None)
+        .unwrap()
+        .timeout_global(Duration::from_secs(5))
+        .agent(agent.clone())
+        .build()
+        .expect_err("expected conflict");
+    match error {
+        Error::ConfigConflict { message } => assert!(message.contains("timeout_global()")),
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    Client::builder("http://localhost", Auth::None)
+        .unwrap()
+        .agent(agent)
+        .build()
+        .expect("custom agent alone is accepted");
+}
+
+#[test]
+fn proxy_routes_requests_through_the_proxy() {
+    let (proxy_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "CONNECT");
+        assert_eq!(request.path, "releasy.invalid:80");
+        assert!(request.headers.contains_key("proxy-authorization"));
+        ResponseSpec {
+            status_line: "HTTP/1.1 502 Bad Gateway".to_string(),
+            headers: vec![],
+            body: "".to_string(),
+        }
+    });
+    let proxy = proxy_url.replace("http://", "http://user:hunter2@");
+
+    let client = Client::builder("http://releasy.invalid", Auth::None)
+        .unwrap()
+        .proxy(proxy.clone())
+        .retry_policy(RetryPolicy::new(0))
+        .build()
+        .unwrap();
+    client.health_check().expect_err("proxy refuses the tunnel");
+    handle.join().expect("server join");
+
+    let mut config = ClientConfig::new("http://releasy.invalid", Auth::None);
+    config.proxy = Some(proxy);
+    let json = serde_json::to_string(&config).unwrap();
+    assert!(!json.contains("hunter2"), "{json}");
+    assert!(json.contains("user:[redacted]@127.0.0.1"), "{json}");
+
+    let error = Client::builder("http://releasy.invalid", Auth::None)
+        .unwrap()
+        .proxy("http://[::1")
+        .build()
+        .expect_err("invalid proxy");
+    assert!(matches!(error, Error::Validation { .. }), "{error:?}");
+
+    let agent: ureq::Agent = ureq::Agent::config_builder().build().into();
+    let error = Client::builder("http://releasy.invalid", Auth::None)
+        .unwrap()
+        .proxy("http://127.0.0.1:3128")
+        .agent(agent)
+        .build()
+        .expect_err("expected conflict");
+    match error {
+        Error::ConfigConflict { message } => assert!(message.contains("proxy()"), "{message}"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn socket_tuning_options_build_a_working_client() {
+    let (base_url, handle) = spawn_server_sequence(2, move |request| {
+        assert_eq!(request.path, "/health");
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"status":"ok"}"#.to_string(),
+        }
+    });
+
+    for nodelay in [true, false] {
+        let client = Client::builder(base_url.clone(), Auth::None)
+            .unwrap()
+            .tcp_nodelay(nodelay)
+            .pool_idle_timeout(Some(Duration::from_secs(30)))
+            .build()
+            .unwrap();
+        client.health_check().unwrap();
+    }
+
+    // Both are agent-level settings and are ignored for a custom agent.
+    let agent: ureq::Agent = ureq::Agent::config_builder().build().into();
+    Client::builder("http://localhost", Auth::None)
+        .unwrap()
+        .tcp_nodelay(false)
+        .pool_idle_timeout(None)
+        .agent(agent)
+        .build()
+        .expect("custom agent accepted");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn measure_latency_samples_live_endpoint() {
+    let (base_url, handle) = spawn_server_sequence(4, move |request| {
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/live");
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"status":"ok"}"#.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::None).unwrap();
+    let stats = client.measure_latency(4).expect("latency");
+    assert_eq!(stats.samples, 4);
+    assert_eq!(client.stats().requests_total, 4);
+    assert!(stats.min > Duration::ZERO);
+    assert!(stats.min <= stats.p50 && stats.p50 <= stats.max);
+    assert!(stats.min <= stats.mean && stats.mean <= stats.max);
+
+    handle.join().expect("server join");
+}
+
+struct CaptureLogger {
+    records: Mutex<Vec<String>>,
+}
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target().starts_with("releasy_client")
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            self.records.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger {
+    records: Mutex::new(Vec::new()),
+};
+
+/// Install the capturing logger once and return log lines mentioning `needle`.
+fn captured_logs(needle: &str) -> Vec<String> {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&LOGGER).expect("install logger");
+        log::set_max_level(log::LevelFilter::Debug);
+    });
+    LOGGER
+        .records
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|line| line.contains(needle))
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn log_bodies_redacts_created_api_key() {
+    captured_logs("");
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.path, "/v1/admin/keys");
+        let body = r#"{"api_key_id":"key-logged","api_key":"sk-live-secret","customer_id":"cust-log","key_type":"deploy","scopes":[]}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 201 Created".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::builder(base_url, Auth::AdminKey("admin-key".to_string()))
+        .unwrap()
+        .log_bodies(true)
+        .build()
+        .unwrap();
+    let request = AdminCreateKeyRequest {
+        customer_id: "cust-log".into(),
+        expires_at: None,
+        key_type: None,
+        name: None,
+        scopes: None,
+    };
+    let response = client.admin_create_key(&request).unwrap();
+    assert_eq!(response.api_key, "sk-live-secret");
+
+    let lines = captured_logs("cust-log");
+    assert_eq!(lines.len(), 2, "request and response bodies: {lines:?}");
+    assert!(lines[0].contains("POST") && lines[0].contains("request body"));
+    assert!(lines[1].contains("response 201 body"));
+    assert!(lines[1].contains(r#""api_key":"[redacted]""#));
+    assert!(lines[1].contains(r#""api_key_id":"key-logged""#));
+    assert!(lines.iter().all(|line| !line.contains("sk-live-secret")));
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn log_bodies_redacts_reset_link() {
+    captured_logs("");
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.path, "/v1/admin/users/user-log/reset-credentials");
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"reset_link":"https://id.example.com/reset/one-time-secret"}"#.to_string(),
+        }
+    });
+
+    let client = Client::builder(base_url, Auth::AdminKey("admin-key".to_string()))
+        .unwrap()
+        .log_bodies(true)
+        .build()
+        .unwrap();
+    let request = ResetCredentialsRequest {
+        send_email: Some(false),
+    };
+    let response = client.reset_credentials("user-log", &request).unwrap();
+    assert_eq!(
+        response.map(|response| response.reset_link),
+        Some("https://id.example.com/reset/one-time-secret".to_string())
+    );
+
+    let lines = captured_logs("user-log/reset-credentials");
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.contains(r#""reset_link":"[redacted]""#)),
+        "{lines:?}"
+    );
+    assert!(lines.iter().all(|line| !line.contains("one-time-secret")));
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn retries_are_logged_with_cause_and_delay() {
+    captured_logs("");
+    let mut attempts = 0;
+    let (base_url, handle) = spawn_server_sequence(3, move |_request| {
+        attempts += 1;
+        match attempts {
+            1 => ResponseSpec {
+                status_line: "HTTP/1.1 503 Service Unavailable".to_string(),
+                headers: vec![("Retry-After".to_string(), "0".to_string())],
+                body: "".to_string(),
+            },
+            2 => ResponseSpec {
+                status_line: "HTTP/1.1 429 Too Many Requests".to_string(),
+                headers: vec![("Retry-After".to_string(), "0".to_string())],
+                body: "".to_string(),
+            },
+            _ => ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body: r#"{"releases":[],"limit":50,"offset":0}"#.to_string(),
+            },
+        }
+    });
+
+    let client = Client::builder(base_url, Auth::None)
+        .unwrap()
+        .retry_policy(RetryPolicy::new(4))
+        .build()
+        .unwrap();
+    client
+        .list_releases(ReleaseListQuery::default())
+        .expect("succeeds after retries");
+
+    // Other tests log through the same logger, so look for these lines only.
+    let lines = captured_logs("retrying GET /v1/releases (retry");
+    let expected = [
+        "retrying GET /v1/releases (retry 1 of 4) after status 503; backing off 0ns",
+        "retrying GET /v1/releases (retry 2 of 4) after status 429; backing off 0ns",
+    ];
+    for line in expected {
+        assert!(lines.iter().any(|logged| logged == line), "{lines:?}");
+    }
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn client_from_fully_populated_config() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.path, "/health");
+        assert!(
+            request
+                .headers
+                .get("host")
+                .expect("host header")
+                .starts_with("releasy.invalid:")
+        );
+        assert_eq!(
+            request.headers.get("user-agent"),
+            Some(&"config-test/1.0".to_string())
+        );
+        assert_eq!(
+            request.headers.get("x-releasy-api-key"),
+            Some(&"test-key".to_string())
+        );
+
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"status":"ok"}"#.to_string(),
+        }
+    });
+
+    let addr: std::net::SocketAddr = base_url
+        .trim_start_matches("http://")
+        .parse()
+        .expect("socket addr");
+    let config = ClientConfig {
+        base_url: format!("http://releasy.invalid:{}/", addr.port()),
+        auth: Auth::ApiKey("test-key".to_string()),
+        user_agent: Some("config-test/1.0".to_string()),
+        api_version: None,
+        timeout_global: Some(Duration::from_secs(5)),
+        overall_deadline: None,
+        resolve_overrides: vec![("releasy.invalid".to_string(), addr)],
+        max_redirects: Some(3),
+        proxy: None,
+        tcp_nodelay: None,
+        pool_idle_timeout: None,
+        capture_request_body_on_error: true,
+        strict_deserialization: true,
+        log_bodies: false,
+        compress_requests: false,
+        max_page_size: None,
+        transfer_buffer_size: None,
+        max_upload_bytes: None,
+        retry: Some(RetryPolicy::new(1)),
+        retry_budget: Some(RetryBudget {
+            ratio: 0.1,
+            min_per_sec: 5,
+        }),
+        circuit_breaker: None,
+        allowed_download_hosts: Some(vec!["*.cdn.example.com".to_string()]),
+        response_cache: None,
+    };
+
+    let mut json = serde_json::to_value(&config).unwrap();
+    assert_eq!(json["auth"], serde_json::json!({ "kind": "api" }));
+    assert!(!json.to_string().contains("test-key"));
+    assert!(serde_json::from_value::<ClientConfig>(json.clone()).is_err());
+    json["auth"]["secret"] = "test-key".into();
+    let restored: ClientConfig = serde_json::from_value(json).unwrap();
+    assert_eq!(restored, config);
+
+    let client = Client::from_config(config).unwrap();
+    assert_eq!(
+        client.base_url(),
+        format!("http://releasy.invalid:{}", addr.port())
+    );
+    assert_eq!(client.auth_kind(), "api");
+    client.health_check().unwrap();
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn create_entitlement_rejects_inverted_window_before_sending() {
+    let client = Client::new(
+        "http://localhost:1",
+        Auth::AdminKey("admin-key".to_string()),
+    )
+    .unwrap();
+    let request = EntitlementCreateRequest {
+        product: "demo".to_string(),
+        starts_at: 1_700_000_000,
+        ends_at: Some(1_600_000_000),
+        metadata: None,
+    };
+
+    let error = client
+        .create_entitlement("cust-1", &request)
+        .expect_err("expected validation error");
+    match error {
+        Error::Validation { message } => assert!(message.contains("ends_at")),
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert_eq!(client.stats().requests_total, 0);
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::Digest;
+    sha2::Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[test]
+fn request_signer_headers_cover_method_path_and_body() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "POST");
+        let mut canonical = b"POST\n/v1/releases\n1700000000\n".to_vec();
+        canonical.extend_from_slice(&request.body);
+        assert_eq!(
+            request.headers.get("x-signature"),
+            Some(&sha256_hex(&canonical))
+        );
+        assert_eq!(
+            request.headers.get("x-timestamp"),
+            Some(&"1700000000".to_string())
+        );
+        assert_eq!(
+            request.headers.get("x-releasy-admin-key"),
+            Some(&"admin-key".to_string())
+        );
+
+        let body = r#"{"id":"rel-1","product":"demo","version":"1.0.0","status":"draft","created_at":1700000000}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 201 Created".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::builder(base_url, Auth::AdminKey("admin-key".to_string()))
+        .unwrap()
+        .request_signer(|request| {
+            let timestamp = "1700000000";
+            let mut canonical =
+                format!("{}\n{}\n{}\n", request.method, request.path, timestamp).into_bytes();
+            canonical.extend_from_slice(request.body);
+            vec![
+                ("x-timestamp".to_string(), timestamp.to_string()),
+                ("x-signature".to_string(), sha256_hex(&canonical)),
+            ]
+        })
+        .build()
+        .unwrap();
+    let request = ReleaseCreateRequest {
+        product: "demo".to_string(),
+        version: "1.0.0".to_string(),
+    };
+    let release = client.create_release(&request).unwrap();
+    assert_eq!(release.id, "rel-1");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn request_signer_sees_list_query_string() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.path, "/v1/releases?product=demo&status=published");
+        assert_eq!(
+            request.headers.get("x-signed-path"),
+            Some(&request.path.clone())
+        );
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"releases":[],"limit":50,"offset":0}"#.to_string(),
+        }
+    });
+
+    let client = Client::builder(base_url, Auth::AdminKey("admin-key".to_string()))
+        .unwrap()
+        .request_signer(|request| vec![("x-signed-path".to_string(), request.path.to_string())])
+        .build()
+        .unwrap();
+    let query = ReleaseListQuery {
+        product: Some("demo".to_string()),
+        status: vec!["published".to_string()],
+        ..ReleaseListQuery::default()
+    };
+    client.list_releases(query).unwrap();
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn request_signer_sees_multipart_upload_body() {
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+    let path = write_temp_file(b"releasy-multipart-bytes");
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.path, "/v1/releases/rel-1/artifacts/upload");
+        assert!(!request.body.is_empty());
+        assert_eq!(
+            request.headers.get("content-length"),
+            Some(&request.body.len().to_string())
+        );
+        assert_eq!(
+            request.headers.get("x-signed-body"),
+            Some(&hex(&request.body))
+        );
+        ResponseSpec {
+            status_line: "HTTP/1.1 201 Created".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"id":"art-1","release_id":"rel-1","object_key":"releases/rel-1/a.bin","checksum":"abc","size":23,"platform":"linux","created_at":1}"#.to_string(),
+        }
+    });
+
+    let client = Client::builder(base_url, Auth::AdminKey("admin-key".to_string()))
+        .unwrap()
+        .request_signer(|request| vec![("x-signed-body".to_string(), hex(request.body))])
+        .build()
+        .unwrap();
+    client
+        .upload_artifact_multipart("rel-1", &path, &Platform::Linux)
+        .expect("upload");
+
+    handle.join().expect("server join");
+    let _ = std::fs::remove_file(path);
+}
+
+fn spawn_range_server(honor_range: bool) -> (String, thread::JoinHandle<()>) {
+    spawn_server_sequence(3, move |request| match request.path.as_str() {
+        "/v1/downloads/token" => ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"download_url":"/v1/downloads/tok-1","expires_at":1700000000}"#.to_string(),
+        },
+        "/v1/downloads/tok-1" => {
+            let host = request.headers.get("host").expect("host header");
+            ResponseSpec {
+                status_line: "HTTP/1.1 302 Found".to_string(),
+                headers: vec![(
+                    "Location".to_string(),
+                    format!("http://{}/files/artifact.bin", host),
+                )],
+                body: "".to_string(),
+            }
+        }
+        "/files/artifact.bin" => {
+            assert_eq!(request.headers.get("range"), Some(&"bytes=9-".to_string()));
+            if honor_range {
+                ResponseSpec {
+                    status_line: "HTTP/1.1 206 Partial Content".to_string(),
+                    headers: vec![("Content-Range".to_string(), "bytes 9-13/14".to_string())],
+                    body: "bytes".to_string(),
+                }
+            } else {
+                ResponseSpec {
+                    status_line: "HTTP/1.1 200 OK".to_string(),
+                    headers: vec![],
+                    body: "artifact-bytes".to_string(),
+                }
+            }
+        }
+        other => panic!("unexpected path: {other}"),
+    })
+}
+
+fn range_token_request() -> DownloadTokenRequest {
+    DownloadTokenRequest {
+        artifact_id: "art-1".into(),
+        expires_in_seconds: None,
+        purpose: None,
+    }
+}
+
+#[test]
+fn download_range_appends_partial_content() {
+    let (base_url, handle) = spawn_range_server(true);
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let mut output = std::io::Cursor::new(b"artifact-".to_vec());
+
+    let result = client
+        .download_range(&range_token_request(), 9, &mut output, RangeFallback::Error)
+        .unwrap();
+
+    assert_eq!(
+        result,
+        RangeDownload {
+            start: 9,
+            written: 5
+        }
+    );
+    assert_eq!(output.into_inner(), b"artifact-bytes");
+    handle.join().expect("server join");
+}
+
+#[test]
+fn download_range_reports_ignored_range() {
+    let (base_url, handle) = spawn_range_server(false);
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let mut output = std::io::Cursor::new(b"artifact-".to_vec());
+
+    let error = client
+        .download_range(&range_token_request(), 9, &mut output, RangeFallback::Error)
+        .expect_err("expected error");
+
+    assert!(matches!(error, Error::RangeNotSupported));
+    assert_eq!(output.into_inner(), b"artifact-");
+    handle.join().expect("server join");
+}
+
+#[test]
+fn download_range_restarts_when_range_ignored() {
+    let (base_url, handle) = spawn_range_server(false);
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let mut output = std::io::Cursor::new(b"stale-data".to_vec());
+
+    let result = client
+        .download_range(
+            &range_token_request(),
+            9,
+            &mut output,
+            RangeFallback::Restart,
+        )
+        .unwrap();
+
+    assert_eq!(
+        result,
+        RangeDownload {
+            start: 0,
+            written: 14
+        }
+    );
+    assert_eq!(output.into_inner(), b"artifact-bytes");
+    handle.join().expect("server join");
+}
+
+#[test]
+fn clients_can_share_an_agent() {
+    let (base_url, handle) = spawn_server_sequence(2, move |request| {
+        let key = request
+            .headers
+            .get("x-releasy-api-key")
+            .expect("api key header");
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: format!(
+                r#"{{"active":true,"api_key_id":"{key}","customer_id":"cust-1","key_type":"api","scopes":[]}}"#
+            ),
+        }
+    });
+
+    let first = Client::new(base_url.clone(), Auth::ApiKey("tenant-a".to_string())).unwrap();
+    let second = Client::builder(base_url, Auth::ApiKey("tenant-b".to_string()))
+        .unwrap()
+        .agent(first.agent())
+        .build()
+        .unwrap();
+
+    assert_eq!(first.auth_introspect().unwrap().api_key_id, "tenant-a");
+    assert_eq!(second.auth_introspect().unwrap().api_key_id, "tenant-b");
+    // Connections opened through the shared agent are counted by its owner.
+    assert_eq!(first.stats().new_connections, 2);
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn idempotent_create_reports_replays() {
+    let (base_url, handle) = spawn_server_sequence(2, move |request| {
+        assert_eq!(request.path, "/v1/admin/customers");
+        assert_eq!(
+            request.headers.get("idempotency-key"),
+            Some(&"idem-123".to_string())
+        );
+        let mut headers = vec![("Content-Type".to_string(), "application/json".to_string())];
+        if request.headers.get("user-agent") == Some(&"retry".to_string()) {
+            headers.push(("Idempotent-Replayed".to_string(), "true".to_string()));
+        }
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers,
+            body: r#"{"id":"cust-1","name":"Acme","created_at":1700000000}"#.to_string(),
+        }
+    });
+
+    let request = AdminCreateCustomerRequest {
+        name: "Acme".to_string(),
+        plan: None,
+    };
+    let client = Client::new(base_url.clone(), Auth::AdminKey("admin-key".to_string())).unwrap();
+    let first = client
+        .admin_create_customer_idempotent(&request, "idem-123")
+        .unwrap();
+    assert!(!first.replayed);
+    assert_eq!(first.value.id, "cust-1");
+
+    let retry = Client::builder(base_url, Auth::AdminKey("admin-key".to_string()))
+        .unwrap()
+        .user_agent("retry")
+        .build()
+        .unwrap();
+    let second = retry
+        .admin_create_customer_idempotent(&request, "idem-123")
+        .unwrap();
+    assert!(second.replayed);
+    assert_eq!(second.value, first.value);
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn idempotent_user_create_treats_200_as_replay() {
+    let (base_url, handle) = spawn_server(move |_request| {
+        let body = r#"{"id":"user-2","keycloak_user_id":"kc-2","customer_id":"cust-2","email":"bob","status":"active","groups":[],"created_at":1700000005,"updated_at":1700001005}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let request = UserCreateRequest {
+        email: "bob".to_string(),
+        customer_id: "cust-2".into(),
+        display_name: None,
+        groups: None,
+        metadata: None,
+        status: None,
+    };
+    let response = client
+        .create_user_idempotent(&request, "idem-user-1")
+        .unwrap();
+    assert!(response.replayed);
+    assert_eq!(response.value.id, "user-2");
+
+    handle.join().expect("server join");
+}
+
+fn spawn_status_server(
+    count: usize,
+    status_line: &'static str,
+) -> (String, thread::JoinHandle<()>) {
+    spawn_server_sequence(count, move |_request| ResponseSpec {
+        status_line: status_line.to_string(),
+        headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+        body: r#"{"error":{"code":"unavailable","message":"try later"}}"#.to_string(),
+    })
+}
+
+#[test]
+fn retry_policy_retries_transient_failures_of_idempotent_requests() {
+    let mut attempts = 0;
+    let (base_url, handle) = spawn_server_sequence(3, move |request| {
+        attempts += 1;
+        match attempts {
+            1 => ResponseSpec {
+                status_line: "HTTP/1.1 503 Service Unavailable".to_string(),
+                headers: vec![("Retry-After".to_string(), "0".to_string())],
+                body: "".to_string(),
+            },
+            2 => {
+                assert_eq!(request.method, "GET");
+                ResponseSpec {
+                    status_line: "HTTP/1.1 200 OK".to_string(),
+                    headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                    body: r#"{"status":"ok"}"#.to_string(),
+                }
+            }
+            _ => {
+                assert_eq!(request.method, "POST");
+                ResponseSpec {
+                    status_line: "HTTP/1.1 503 Service Unavailable".to_string(),
+                    headers: vec![],
+                    body: "".to_string(),
+                }
+            }
+        }
+    });
+
+    let client = Client::builder(base_url, Auth::ApiKey("test-key".to_string()))
+        .unwrap()
+        .retry_policy(RetryPolicy::new(3).initial_backoff(Duration::from_millis(1)))
+        .build()
+        .unwrap();
+
+    client.live_check().unwrap();
+    // POST without an idempotency key is not retried.
+    let error = client.auth_introspect().expect_err("expected error");
+    assert_eq!(error.status(), Some(503));
+
+    let stats = client.stats();
+    assert_eq!(stats.retries, 1);
+    assert_eq!(stats.requests_total, 3);
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn retry_budget_stops_retries_once_spent() {
+    // One banked token: only the first failing request gets a retry.
+    let (base_url, handle) = spawn_status_server(4, "HTTP/1.1 503 Service Unavailable");
+    let client = Client::builder(base_url, Auth::ApiKey("test-key".to_string()))
+        .unwrap()
+        .retry_policy(RetryPolicy::new(3).initial_backoff(Duration::ZERO))
+        .retry_budget(0.0, 1)
+        .clock(ManualClock::new(UNIX_EPOCH))
+        .build()
+        .unwrap();
+    let clone = client.clone();
+
+    for client in [&client, &clone, &client] {
+        let error = client.live_check().expect_err("expected error");
+        assert_eq!(error.status(), Some(503));
+    }
+
+    assert_eq!(client.stats().retries, 1);
+    assert_eq!(client.stats().requests_total, 4);
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn circuit_breaker_opens_after_failures_and_closes_after_trial() {
+    let statuses = [503, 503, 503, 200, 200];
+    let mut served = 0;
+    let (base_url, handle) = spawn_server_sequence(statuses.len(), move |_| {
+        let status = statuses[served];
+        served += 1;
+        if status == 200 {
+            ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body: r#"{"status":"ok"}"#.to_string(),
+            }
+        } else {
+            ResponseSpec {
+                status_line: "HTTP/1.1 503 Service Unavailable".to_string(),
+                headers: vec![],
+                body: "".to_string(),
+            }
+        }
+    });
+    let clock = ManualClock::new(UNIX_EPOCH);
+    let client = Client::builder(base_url, Auth::None)
+        .unwrap()
+        .circuit_breaker(2, Duration::from_secs(30))
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    let clone = client.clone();
+
+    for _ in 0..2 {
+        assert_eq!(client.health_check().unwrap_err().status(), Some(503));
+    }
+    // Open: clones share the state and nothing reaches the server.
+    assert!(matches!(clone.health_check(), Err(Error::CircuitOpen)));
+    clock.advance(Duration::from_secs(29));
+    assert!(matches!(client.health_check(), Err(Error::CircuitOpen)));
+
+    // A failed half-open trial reopens the circuit.
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(client.health_check().unwrap_err().status(), Some(503));
+    assert!(matches!(client.health_check(), Err(Error::CircuitOpen)));
+
+    // A successful trial closes it again.
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(clone.health_check().unwrap().status, "ok");
+    assert_eq!(client.health_check().unwrap().status, "ok");
+    assert_eq!(client.stats().requests_total, 5);
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn open_circuit_rejects_uploads_and_token_resolution() {
+    let path = write_temp_file(b"releasy-multipart-bytes");
+    let (base_url, handle) = spawn_status_server(1, "HTTP/1.1 503 Service Unavailable");
+    let client = Client::builder(base_url, Auth::None)
+        .unwrap()
+        .circuit_breaker(1, Duration::from_secs(30))
+        .clock(ManualClock::new(UNIX_EPOCH))
+        .build()
+        .unwrap();
+
+    assert_eq!(client.health_check().unwrap_err().status(), Some(503));
+    assert!(matches!(
+        client.upload_artifact_multipart("rel-1", &path, &Platform::Linux),
+        Err(Error::CircuitOpen)
+    ));
+    assert!(matches!(
+        client.resolve_download_token("tok-1"),
+        Err(Error::CircuitOpen)
+    ));
+    assert_eq!(client.stats().requests_total, 1);
+
+    handle.join().expect("server join");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn overall_deadline_stops_retrying_before_backoff_overruns() {
+    let (base_url, handle) = spawn_status_server(2, "HTTP/1.1 503 Service Unavailable");
+    let clock = ManualClock::new(UNIX_EPOCH);
+    let client = Client::builder(base_url, Auth::None)
+        .unwrap()
+        .retry_policy(
+            RetryPolicy::new(5)
+                .initial_backoff(Duration::from_secs(1))
+                .max_backoff(Duration::from_secs(10))
+                .jitter(Jitter::None),
+        )
+        .overall_deadline(Duration::from_millis(2500))
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+
+    // Backoffs of 1s then 2s: the second would end past the 2.5s deadline.
+    let error = client.live_check().expect_err("expected timeout");
+    assert!(
+        matches!(
+            &error,
+            Error::Timeout {
+                method,
+                timeout: ureq::Timeout::Global,
+                ..
+            } if method == "GET"
+        ),
+        "{error:?}"
+    );
+    assert_eq!(client.stats().requests_total, 2);
+    assert_eq!(client.stats().retries, 1);
+    assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(1));
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn manual_clock_replaces_backoff_sleeps() {
+    let mut attempts = 0;
+    let (base_url, handle) = spawn_server_sequence(2, move |_request| {
+        attempts += 1;
+        if attempts == 1 {
+            ResponseSpec {
+                status_line: "HTTP/1.1 503 Service Unavailable".to_string(),
+                headers: vec![("Retry-After".to_string(), "30".to_string())],
+                body: "".to_string(),
+            }
+        } else {
+            ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body: r#"{"status":"ok"}"#.to_string(),
+            }
+        }
+    });
+    let clock = ManualClock::new(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+    let client = Client::builder(base_url, Auth::ApiKey("test-key".to_string()))
+        .unwrap()
+        .retry_policy(RetryPolicy::new(1).max_backoff(Duration::from_secs(60)))
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    let start = client.now();
+
+    // Retry-After is honored by advancing the clock instead of blocking.
+    client.live_check().unwrap();
+    assert_eq!(clock.now(), start + Duration::from_secs(30));
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn retry_budget_refills_with_clock_time() {
+    let (base_url, handle) = spawn_status_server(5, "HTTP/1.1 503 Service Unavailable");
+    let clock = ManualClock::new(UNIX_EPOCH);
+    let client = Client::builder(base_url, Auth::ApiKey("test-key".to_string()))
+        .unwrap()
+        .retry_policy(RetryPolicy::new(1).initial_backoff(Duration::ZERO))
+        .retry_budget(0.0, 1)
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+
+    client.live_check().expect_err("expected error");
+    client.live_check().expect_err("expected error");
+    assert_eq!(client.stats().retries, 1);
+
+    // A second later one more token has accrued.
+    clock.advance(Duration::from_secs(1));
+    client.live_check().expect_err("expected error");
+    assert_eq!(client.stats().retries, 2);
+    assert_eq!(client.stats().requests_total, 5);
+
+    handle.join().expect("server join");
+}
+
+fn spawn_customer_server(
+    count: usize,
+    cache_control: Option<&'static str>,
+) -> (String, thread::JoinHandle<()>) {
+    spawn_server_sequence(count, move |request| {
+        assert_eq!(request.path, "/v1/admin/customers/cust-1");
+        let mut headers = vec![("Content-Type".to_string(), "application/json".to_string())];
+        if let Some(value) = cache_control {
+            headers.push(("Cache-Control".to_string(), value.to_string()));
+        }
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers,
+            body: r#"{"id":"cust-1","name":"Acme","created_at":1700000000,"plan":"pro","suspended_at":null}"#.to_string(),
+        }
+    })
+}
+
+#[test]
+fn response_cache_serves_repeated_gets() {
+    let (base_url, handle) = spawn_customer_server(1, None);
+    let client = Client::builder(base_url, Auth::AdminKey("admin".to_string()))
+        .unwrap()
+        .response_cache(8, Duration::from_secs(60))
+        .clock(ManualClock::new(UNIX_EPOCH))
+        .build()
+        .unwrap();
+    let clone = client.clone();
+
+    let first = client.get_customer("cust-1").unwrap();
+    let second = clone.get_customer("cust-1").unwrap();
+
+    // The server accepts a single connection, so the clone was served from cache.
+    assert_eq!(first, second);
+    handle.join().expect("server join");
+}
+
+#[test]
+fn response_cache_refetches_after_ttl() {
+    let (base_url, handle) = spawn_customer_server(2, None);
+    let clock = ManualClock::new(UNIX_EPOCH);
+    let client = Client::builder(base_url, Auth::AdminKey("admin".to_string()))
+        .unwrap()
+        .response_cache(8, Duration::from_secs(60))
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+
+    client.get_customer("cust-1").unwrap();
+    clock.advance(Duration::from_secs(60));
+    client.get_customer("cust-1").unwrap();
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn response_cache_skips_no_store_responses() {
+    let (base_url, handle) = spawn_customer_server(2, Some("private, no-store"));
+    let client = Client::builder(base_url, Auth::AdminKey("admin".to_string()))
+        .unwrap()
+        .response_cache(8, Duration::from_secs(60))
+        .build()
+        .unwrap();
+
+    client.get_customer("cust-1").unwrap();
+    client.get_customer("cust-1").unwrap();
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn response_cache_conflicts_with_custom_agent() {
+    let error = Client::builder("http://localhost", Auth::None)
+        .unwrap()
+        .agent(ureq::Agent::new_with_defaults())
+        .response_cache(8, Duration::from_secs(60))
+        .build()
+        .expect_err("expected conflict");
+
+    assert!(error.to_string().contains("response_cache()"));
+}
+
+#[test]
+fn api_version_header_is_sent_and_echo_recorded() {
+    let (base_url, handle) = spawn_server(|request| {
+        assert_eq!(
+            request.headers.get("x-releasy-api-version"),
+            Some(&"2".to_string())
+        );
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("X-Releasy-Api-Version".to_string(), "2.3".to_string()),
+            ],
+            body: r#"{"status":"ok"}"#.to_string(),
+        }
+    });
+    let client = Client::builder(base_url, Auth::None)
+        .unwrap()
+        .api_version("2")
+        .build()
+        .unwrap();
+    assert_eq!(client.server_api_version(), None);
+
+    client.live_check().unwrap();
+
+    assert_eq!(client.server_api_version().as_deref(), Some("2.3"));
+    handle.join().expect("server join");
+}
+
+#[test]
+fn api_version_header_is_omitted_by_default() {
+    let (base_url, handle) = spawn_server(|request| {
+        assert!(!request.headers.contains_key("x-releasy-api-version"));
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"status":"ok"}"#.to_string(),
+        }
+    });
+    let client = Client::new(base_url, Auth::None).unwrap();
+
+    client.live_check().unwrap();
+
+    assert_eq!(client.server_api_version(), None);
+    handle.join().expect("server join");
+}
+
+#[test]
+fn last_request_duration_covers_server_delay() {
+    let (base_url, handle) = spawn_server(|_request| {
+        thread::sleep(Duration::from_millis(150));
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"status":"ok"}"#.to_string(),
+        }
+    });
+    let client = Client::new(base_url, Auth::None).unwrap();
+    assert_eq!(client.last_request_duration(), None);
+
+    let started = std::time::Instant::now();
+    client.live_check().unwrap();
+    let total = started.elapsed();
+
+    let measured = client.last_request_duration().expect("duration recorded");
+    assert!(measured >= Duration::from_millis(150), "{measured:?}");
+    assert!(measured <= total, "{measured:?} > {total:?}");
+    handle.join().expect("server join");
+}
+
+fn spawn_redirect_server(
+    count: usize,
+    location_host: &'static str,
+) -> (String, thread::JoinHandle<()>) {
+    spawn_server_sequence(count, move |request| match request.path.as_str() {
+        "/v1/downloads/token" => ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"download_url":"/v1/downloads/tok-1","expires_at":1700000000}"#.to_string(),
+        },
+        "/v1/downloads/tok-1" => {
+            let host = request.headers.get("host").expect("host header");
+            let port = host.rsplit(':').next().expect("port");
+            ResponseSpec {
+                status_line: "HTTP/1.1 302 Found".to_string(),
+                headers: vec![(
+                    "Location".to_string(),
+                    format!("http://{}:{}/files/artifact.bin", location_host, port),
+                )],
+                body: "".to_string(),
+            }
+        }
+        "/files/artifact.bin" => ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![],
+            body: "artifact-bytes".to_string(),
+        },
+        other => panic!("unexpected path: {other}"),
+    })
+}
+
+#[test]
+fn allowed_download_hosts_permit_listed_redirects() {
+    let (base_url, handle) = spawn_redirect_server(3, "localhost");
+    let client = Client::builder(base_url, Auth::ApiKey("test-key".to_string()))
+        .unwrap()
+        .allowed_download_hosts(vec!["LOCALHOST".to_string()])
+        .build()
+        .unwrap();
+
+    let bytes = client
+        .download_artifact_bytes(&range_token_request())
+        .unwrap();
+    assert_eq!(bytes, b"artifact-bytes");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn allowed_download_hosts_block_other_redirects() {
+    // Only the token and resolve requests reach the server.
+    let (base_url, handle) = spawn_redirect_server(2, "metadata.internal");
+    let client = Client::builder(base_url, Auth::ApiKey("test-key".to_string()))
+        .unwrap()
+        .allowed_download_hosts(["*.cdn.example.com"])
+        .build()
+        .unwrap();
+
+    let error = client
+        .download_artifact_bytes(&range_token_request())
+        .expect_err("expected error");
+    assert_eq!(error.download_stage(), Some(DownloadStage::Fetch));
+    match error {
+        Error::Download { source, .. } => match *source {
+            Error::DisallowedHost { host } => assert_eq!(host, "metadata.internal"),
+            other => panic!("unexpected error: {other:?}"),
+        },
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn allowed_download_hosts_apply_to_storage_redirects() {
+    // The allowed storage host redirects first within itself, then off the
+    // allowlist; the last hop is never requested.
+    let (base_url, handle) = spawn_server_sequence(4, move |request| match request.path.as_str() {
+        "/v1/downloads/token" => ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"download_url":"/v1/downloads/tok-1","expires_at":1700000000}"#.to_string(),
+        },
+        "/v1/downloads/tok-1" => {
+            let host = request.headers.get("host").expect("host header");
+            let port = host.rsplit(':').next().expect("port");
+            ResponseSpec {
+                status_line: "HTTP/1.1 302 Found".to_string(),
+                headers: vec![(
+                    "Location".to_string(),
+                    format!("http://localhost:{}/files/artifact.bin", port),
+                )],
+                body: "".to_string(),
+            }
+        }
+        "/files/artifact.bin" => ResponseSpec {
+            status_line: "HTTP/1.1 302 Found".to_string(),
+            headers: vec![("Location".to_string(), "moved.bin".to_string())],
+            body: "".to_string(),
+        },
+        "/files/moved.bin" => ResponseSpec {
+            status_line: "HTTP/1.1 302 Found".to_string(),
+            headers: vec![(
+                "Location".to_string(),
+                "http://169.254.169.254/latest/meta-data".to_string(),
+            )],
+            body: "".to_string(),
+        },
+        other => panic!("unexpected path: {other}"),
+    });
+    let client = Client::builder(base_url, Auth::ApiKey("test-key".to_string()))
+        .unwrap()
+        .allowed_download_hosts(["localhost"])
+        .build()
+        .unwrap();
+
+    let error = client
+        .download_artifact_bytes(&range_token_request())
+        .expect_err("expected error");
+    match error {
+        Error::Download { source, .. } => match *source {
+            Error::DisallowedHost { host } => assert_eq!(host, "169.254.169.254"),
+            other => panic!("unexpected error: {other:?}"),
+        },
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn allowed_download_hosts_block_presigned_uploads() {
+    let path = write_temp_file(b"payload");
+    let client = Client::builder("http://127.0.0.1:9", Auth::ApiKey("test-key".to_string()))
+        .unwrap()
+        .allowed_download_hosts(["uploads.example.com"])
+        .build()
+        .unwrap();
+
+    let error = client
+        .upload_presigned_artifact("http://169.254.169.254/latest/meta-data", &path)
+        .expect_err("expected error");
+    match error {
+        Error::DisallowedHost { host } => assert_eq!(host, "169.254.169.254"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+fn spawn_disposition_server(disposition: Option<&'static str>) -> (String, thread::JoinHandle<()>) {
+    spawn_server_sequence(3, move |request| match request.path.as_str() {
+        "/v1/downloads/token" => ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"download_url":"/v1/downloads/tok-1","expires_at":1700000000}"#.to_string(),
+        },
+        "/v1/downloads/tok-1" => {
+            let host = request.headers.get("host").expect("host header");
+            ResponseSpec {
+                status_line: "HTTP/1.1 302 Found".to_string(),
+                headers: vec![(
+                    "Location".to_string(),
+                    format!("http://{}/files/blob", host),
+                )],
+                body: "".to_string(),
+            }
+        }
+        "/files/blob" => ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: disposition
+                .map(|value| vec![("Content-Disposition".to_string(), value.to_string())])
+                .unwrap_or_default(),
+            body: "artifact-bytes".to_string(),
+        },
+        other => panic!("unexpected path: {other}"),
+    })
+}
+
+fn temp_download_dir() -> PathBuf {
+    let suffix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("releasy-client-download-{suffix}"));
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+#[test]
+fn download_to_file_names_file_from_content_disposition() {
+    let (base_url, handle) =
+        spawn_disposition_server(Some(r#"attachment; filename="../demo-1.2.3.tar.gz""#));
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let dir = temp_download_dir();
+
+    let metadata = client
+        .download_to_file(&range_token_request(), &dir)
+        .unwrap();
+
+    assert_eq!(metadata.filename.as_deref(), Some("demo-1.2.3.tar.gz"));
+    assert_eq!(metadata.path, dir.join("demo-1.2.3.tar.gz"));
+    assert_eq!(metadata.bytes, 14);
+    assert_eq!(std::fs::read(&metadata.path).unwrap(), b"artifact-bytes");
+
+    std::fs::remove_dir_all(&dir).ok();
+    handle.join().expect("server join");
+}
+
+#[test]
+fn download_to_file_prefers_extended_filename_and_falls_back_to_artifact_id() {
+    let dir = temp_download_dir();
+
+    let (base_url, handle) = spawn_disposition_server(Some(
+        "attachment; filename=\"fallback.bin\"; filename*=UTF-8''demo%20app.zip",
+    ));
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let metadata = client
+        .download_to_file(&range_token_request(), &dir)
+        .unwrap();
+    assert_eq!(metadata.path, dir.join("demo app.zip"));
+    handle.join().expect("server join");
+
+    let (base_url, handle) = spawn_disposition_server(None);
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let metadata = client
+        .download_to_file(&range_token_request(), &dir)
+        .unwrap();
+    assert_eq!(metadata.filename, None);
+    assert_eq!(metadata.path, dir.join("art-1"));
+    handle.join().expect("server join");
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn list_products_parses_summaries() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/v1/products");
+        let body = r#"{"products":[{"name":"demo","latest_version":"1.2.0","release_count":3},{"name":"fresh","release_count":0}]}"#;
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let products = client.list_products().unwrap();
+
+    assert_eq!(
+        products,
+        vec![
+            ProductSummary {
+                name: "demo".to_string(),
+                latest_version: Some("1.2.0".to_string()),
+                release_count: 3,
+            },
+            ProductSummary {
+                name: "fresh".to_string(),
+                latest_version: None,
+                release_count: 0,
+            },
+        ]
+    );
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn openapi_to_writer_streams_the_raw_document() {
+    let document = include_str!("../openapi.json");
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.path, "/openapi.json");
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Transfer-Encoding".to_string(), "chunked".to_string()),
+            ],
+            body: document.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::None).unwrap();
+    let mut buffer = Vec::new();
+    let written = client.openapi_to_writer(&mut buffer).unwrap();
+
+    assert_eq!(written, document.len() as u64);
+    assert_eq!(buffer, document.as_bytes());
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn verify_contract_reports_missing_and_extra_paths() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.path, "/openapi.json");
+        let mut document: serde_json::Value =
+            serde_json::from_str(include_str!("../openapi.json")).expect("openapi");
+        let paths = document["paths"].as_object_mut().expect("paths");
+        paths.remove("/ready");
+        paths.insert("/v1/admin/reports".to_string(), serde_json::json!({}));
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: document.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::None).unwrap();
+    let report = client.verify_contract().unwrap();
+
+    assert_eq!(report.missing, vec!["/ready".to_string()]);
+    assert_eq!(report.extra, vec!["/v1/admin/reports".to_string()]);
+    assert!(!report.is_compatible());
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn create_release_raw_sends_the_value_verbatim() {
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/releases");
+        let body_json: serde_json::Value =
+            serde_json::from_slice(&request.body).expect("json body");
+        assert_eq!(
+            body_json,
+            serde_json::json!({
+                "product": "demo-app",
+                "version": "1.2.3",
+                "channel": "beta",
+            })
+        );
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"id":"rel-1","product":"demo-app","version":"1.2.3","status":"draft","created_at":1700000000}"#.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let body = serde_json::json!({
+        "product": "demo-app",
+        "version": "1.2.3",
+        "channel": "beta",
+    });
+    let release = client.create_release_raw(&body).unwrap();
+    assert_eq!(release.id, "rel-1");
+
+    handle.join().expect("server join");
+}
+
+#[allow(clippy::result_large_err)]
+fn fetch_user(client: &Client, id: String) -> releasy_client::Result<UserResponse> {
+    client.get_user(&id)
+}
+
+#[test]
+fn map_concurrent_preserves_input_order() {
+    let (base_url, handle) = spawn_server_sequence(5, move |request| {
+        let id = request
+            .path
+            .strip_prefix("/v1/admin/users/")
+            .expect("user path")
+            .to_string();
+        if id == "user-3" {
+            return ResponseSpec {
+                status_line: "HTTP/1.1 404 Not Found".to_string(),
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body: r#"{"error":{"code":"not_found","message":"user not found"}}"#.to_string(),
+            };
+        }
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: format!(
+                r#"{{"id":"{id}","keycloak_user_id":"kc","customer_id":"cust-1","email":"{id}@example.com","status":"active","groups":[],"created_at":1700000000,"updated_at":1700000000}}"#
+            ),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let ids = (1..=5).map(|n| format!("user-{n}"));
+    let results = client.map_concurrent(ids, 3, fetch_user);
+
+    assert_eq!(results.len(), 5);
+    for (index, result) in results.iter().enumerate() {
+        let expected = format!("user-{}", index + 1);
+        match result {
+            Ok(user) => assert_eq!(user.id, expected),
+            Err(error) => {
+                assert_eq!(expected, "user-3");
+                assert_eq!(error.status(), Some(404));
+            }
+        }
+    }
+    assert!(results[2].is_err());
+
+    handle.join().expect("server join");
+}
+
+fn spawn_moved_releases_server(count: usize) -> (String, thread::JoinHandle<()>) {
+    spawn_server_sequence(count, move |request| match request.path.as_str() {
+        "/v1/releases" => ResponseSpec {
+            status_line: "HTTP/1.1 301 Moved Permanently".to_string(),
+            headers: vec![("Location".to_string(), "/v2/releases".to_string())],
+            body: "".to_string(),
+        },
+        "/v2/releases" => ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"releases":[],"limit":50,"offset":0}"#.to_string(),
+        },
+        other => panic!("unexpected path: {other}"),
+    })
+}
+
+#[test]
+fn unexpected_redirects_are_followed_by_default() {
+    let (base_url, handle) = spawn_moved_releases_server(2);
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+
+    let releases = client.list_releases(ReleaseListQuery::default()).unwrap();
+    assert!(releases.releases.is_empty());
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn unexpected_redirects_fail_when_not_followed() {
+    let (base_url, handle) = spawn_moved_releases_server(1);
+    let client = Client::builder(base_url, Auth::ApiKey("test-key".to_string()))
+        .unwrap()
+        .max_redirects(0)
+        .build()
+        .unwrap();
+
+    let error = client
+        .list_releases(ReleaseListQuery::default())
+        .expect_err("expected error");
+    match error {
+        Error::UnexpectedRedirect { status, location } => {
+            assert_eq!(status, 301);
+            assert_eq!(location.as_deref(), Some("/v2/releases"));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    handle.join().expect("server join");
+}
+
+#[cfg(feature = "metrics")]
+mod metrics_capture {
+    use std::sync::{Arc, Mutex};
+
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+
+    /// Records every counter increment as `name{label=value,...}`.
+    #[derive(Default)]
+    pub struct CaptureRecorder {
+        pub increments: Arc<Mutex<Vec<String>>>,
+    }
+
+    struct CaptureCounter {
+        key: String,
+        increments: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl CounterFn for CaptureCounter {
+        fn increment(&self, _value: u64) {
+            self.increments.lock().unwrap().push(self.key.clone());
+        }
+
+        fn absolute(&self, _value: u64) {}
+    }
+
+    impl Recorder for CaptureRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let labels: Vec<String> = key
+                .labels()
+                .map(|label| format!("{}={}", label.key(), label.value()))
+                .collect();
+            Counter::from_arc(Arc::new(CaptureCounter {
+                key: format!("{}{{{}}}", key.name(), labels.join(",")),
+                increments: self.increments.clone(),
+            }))
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn metrics_count_requests_by_path_template() {
+    let (base_url, handle) = spawn_customer_server(1, None);
+    let client = Client::new(base_url, Auth::AdminKey("admin".to_string())).unwrap();
+    let recorder = metrics_capture::CaptureRecorder::default();
+
+    let fetched = metrics::with_local_recorder(&recorder, || client.get_customer("cust-1").is_ok());
+
+    assert!(fetched);
+
+    assert_eq!(
+        *recorder.increments.lock().unwrap(),
+        vec![
+            "releasy_requests_total{method=GET,path=/v1/admin/customers/{customer_id},status=200}"
+                .to_string()
+        ]
+    );
+    handle.join().expect("server join");
+}
+
+fn provision_spec() -> CustomerProvisionSpec {
+    CustomerProvisionSpec {
+        customer: AdminCreateCustomerRequest {
+            name: "Acme".to_string(),
+            plan: Some("pro".to_string()),
+        },
+        api_key: Some(AdminCreateKeyRequest {
+            customer_id: CustomerId::default(),
+            expires_at: None,
+            key_type: Some("deploy".to_string()),
+            name: None,
+            scopes: None,
+        }),
+        entitlements: vec![EntitlementCreateRequest::perpetual("demo", 1_700_000_000)],
+        admin_user: Some(UserCreateRequest {
+            email: "admin@acme.test".to_string(),
+            customer_id: CustomerId::default(),
+            display_name: None,
+            groups: Some(vec!["customer_admin".to_string()]),
+            metadata: None,
+            status: None,
+        }),
+    }
+}
+
+fn json_response(status_line: &str, body: &str) -> ResponseSpec {
+    ResponseSpec {
+        status_line: status_line.to_string(),
+        headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+        body: body.to_string(),
+    }
+}
+
+#[test]
+fn provision_customer_creates_every_resource() {
+    let (base_url, handle) = spawn_server_sequence(4, |request| {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).expect("json body");
+        match (request.method.as_str(), request.path.as_str()) {
+            ("POST", "/v1/admin/customers") => json_response(
+                "HTTP/1.1 201 Created",
+                r#"{"id":"cust-9","name":"Acme","created_at":1700000000,"plan":"pro"}"#,
+            ),
+            ("POST", "/v1/admin/keys") => {
+                assert_eq!(body["customer_id"], "cust-9");
+                json_response(
+                    "HTTP/1.1 201 Created",
+                    r#"{"api_key_id":"key-9","api_key":"sk-secret","customer_id":"cust-9","key_type":"deploy","scopes":[]}"#,
+                )
+            }
+            ("POST", "/v1/admin/customers/cust-9/entitlements") => json_response(
+                "HTTP/1.1 201 Created",
+                r#"{"id":"ent-9","customer_id":"cust-9","product":"demo","starts_at":1700000000}"#,
+            ),
+            ("POST", "/v1/admin/users") => {
+                assert_eq!(body["customer_id"], "cust-9");
+                json_response(
+                    "HTTP/1.1 201 Created",
+                    r#"{"id":"user-9","keycloak_user_id":"kc-9","customer_id":"cust-9","email":"admin@acme.test","status":"active","groups":["customer_admin"],"created_at":1700000000,"updated_at":1700000000}"#,
+                )
+            }
+            other => panic!("unexpected request: {other:?}"),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let result = client.provision_customer(&provision_spec()).unwrap();
+
+    assert_eq!(result.customer.id, "cust-9");
+    assert_eq!(result.api_key.unwrap().api_key_id, "key-9");
+    assert_eq!(result.entitlements[0].id, "ent-9");
+    assert_eq!(result.admin_user.unwrap().id, "user-9");
+    handle.join().expect("server join");
+}
+
+#[test]
+fn provision_customer_rolls_back_after_failure() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorded = seen.clone();
+    let (base_url, handle) = spawn_server_sequence(5, move |request| {
+        recorded
+            .lock()
+            .unwrap()
+            .push(format!("{} {}", request.method, request.path));
+        match (request.method.as_str(), request.path.as_str()) {
+            ("POST", "/v1/admin/customers") => json_response(
+                "HTTP/1.1 201 Created",
+                r#"{"id":"cust-9","name":"Acme","created_at":1700000000,"plan":"pro"}"#,
+            ),
+            ("POST", "/v1/admin/keys") => json_response(
+                "HTTP/1.1 201 Created",
+                r#"{"api_key_id":"key-9","api_key":"sk-secret","customer_id":"cust-9","key_type":"deploy","scopes":[]}"#,
+            ),
+            ("POST", "/v1/admin/customers/cust-9/entitlements") => json_response(
+                "HTTP/1.1 400 Bad Request",
+                r#"{"error":{"code":"unknown_product","message":"no such product"}}"#,
+            ),
+            ("POST", "/v1/admin/keys/revoke") => {
+                json_response("HTTP/1.1 200 OK", r#"{"api_key_id":"key-9"}"#)
+            }
+            ("PATCH", "/v1/admin/customers/cust-9") => {
+                let body: serde_json::Value =
+                    serde_json::from_slice(&request.body).expect("json body");
+                assert_eq!(body["suspended"], true);
+                json_response(
+                    "HTTP/1.1 200 OK",
+                    r#"{"id":"cust-9","name":"Acme","created_at":1700000000,"plan":"pro","suspended_at":1700000100}"#,
+                )
+            }
+            other => panic!("unexpected request: {other:?}"),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let error = client
+        .provision_customer(&provision_spec())
+        .expect_err("expected provisioning failure");
+
+    assert_eq!(error.status(), Some(400));
+    match error {
+        Error::ProvisionFailed {
+            customer_id,
+            source,
+            cleanup_errors,
+        } => {
+            assert_eq!(customer_id, "cust-9");
+            assert_eq!(source.api_error().unwrap().error.code, "unknown_product");
+            assert!(cleanup_errors.is_empty(), "{cleanup_errors:?}");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    handle.join().expect("server join");
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [
+            "POST /v1/admin/customers",
+            "POST /v1/admin/keys",
+            "POST /v1/admin/customers/cust-9/entitlements",
+            "POST /v1/admin/keys/revoke",
+            "PATCH /v1/admin/customers/cust-9",
+        ]
+    );
+}
+
+#[test]
+fn prepare_create_release_rejects_empty_product() {
+    let client = Client::new("http://releasy.invalid", Auth::admin_key("admin-key")).unwrap();
+    let request = ReleaseCreateRequest {
+        product: String::new(),
+        version: "1.0.0".to_string(),
+    };
+
+    let error = client
+        .prepare(Operation::CreateRelease(&request))
+        .expect_err("expected validation error");
+    match error {
+        Error::Validation { message } => assert!(message.contains("product"), "{message}"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert!(matches!(
+        client.create_release(&request),
+        Err(Error::Validation { .. })
+    ));
+}
+
+#[test]
+fn prepare_resolves_url_and_masks_credentials() {
+    let client = Client::builder("http://releasy.invalid/", Auth::admin_key("admin-key"))
+        .unwrap()
+        .api_version("2")
+        .build()
+        .unwrap();
+    let request = ReleaseCreateRequest {
+        product: "demo".to_string(),
+        version: "1.0.0".to_string(),
+    };
+
+    let prepared = client.prepare(Operation::CreateRelease(&request)).unwrap();
+    assert_eq!(prepared.method, Method::POST);
+    assert_eq!(prepared.url, "http://releasy.invalid/v1/releases");
+    assert_eq!(
+        prepared.body.as_deref(),
+        Some(r#"{"product":"demo","version":"1.0.0"}"#)
+    );
+    let header = |name: &str| {
+        prepared
+            .headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    };
+    assert_eq!(header("x-releasy-admin-key"), Some("[redacted]"));
+    assert_eq!(header("x-releasy-api-version"), Some("2"));
+    assert_eq!(
+        header("content-type"),
+        Some("application/json; charset=utf-8")
+    );
+
+    let query = ReleaseListQuery {
+        product: Some("demo app".to_string()),
+        extra_query: vec![("tags".to_string(), "a,b".to_string())],
+        ..ReleaseListQuery::default()
+    };
+    let prepared = client.prepare(Operation::ListReleases(&query)).unwrap();
+    assert_eq!(
+        prepared.url,
+        "http://releasy.invalid/v1/releases?product=demo%20app&tags=a%2Cb"
+    );
+    assert_eq!(prepared.body, None);
+
+    // Empty ids are sent as is by the real call, so they are not rejected.
+    let prepared = client
+        .prepare(Operation::PublishRelease("".into()))
+        .unwrap();
+    assert_eq!(prepared.url, "http://releasy.invalid/v1/releases//publish");
+    let query = EntitlementListQuery {
+        include_expired: Some(true),
+        ..EntitlementListQuery::default()
+    };
+    let prepared = client
+        .prepare(Operation::ListEntitlements {
+            customer_id: "cust-1".into(),
+            query: &query,
+        })
+        .unwrap();
+    assert_eq!(
+        prepared.url,
+        "http://releasy.invalid/v1/admin/customers/cust-1/entitlements?include_expired=true"
+    );
+    let anonymous = client.with_auth(Auth::None);
+    assert!(anonymous.prepare(Operation::HealthCheck).is_ok());
+    assert!(matches!(
+        anonymous.prepare(Operation::AuthIntrospect),
+        Err(Error::Validation { .. })
+    ));
+}
+
+#[test]
+fn subscribe_release_events_parses_stream_and_resumes_after_drop() {
+    let release = |id: &str, status: &str| {
+        format!(
+            r#"{{"id":"{id}","product":"demo","version":"1.0.0","status":"{status}","created_at":1}}"#
+        )
+    };
+    let first = format!(
+        ": keep-alive\nretry: 0\n\nid: 1\nevent: release.created\ndata: {{\"release\":{}}}\n\nid: 2\ndata: {{\"type\":\"release.published\",\ndata: \"release\":{}}}\n\n",
+        release("rel-1", "draft"),
+        release("rel-1", "published"),
+    );
+    let second = format!(
+        "id: 3\nevent: release.unpublished\ndata: {{\"release\":{}}}\n\n",
+        release("rel-1", "draft"),
+    );
+    let mut bodies = vec![first, second].into_iter();
+    let mut connection = 0;
+    let (base_url, handle) = spawn_server_sequence(2, move |request| {
+        connection += 1;
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/v1/releases/events");
+        assert_eq!(
+            request.headers.get("accept").map(String::as_str),
+            Some("text/event-stream")
+        );
+        let expected_last_id = (connection == 2).then(|| "2".to_string());
+        assert_eq!(
+            request.headers.get("last-event-id"),
+            expected_last_id.as_ref()
+        );
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "text/event-stream".to_string())],
+            body: bodies.next().expect("body"),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let events: Vec<ReleaseEvent> = client
+        .subscribe_release_events()
+        .unwrap()
+        .take(3)
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let summary: Vec<(Option<&str>, &str, &str)> = events
+        .iter()
+        .map(|event| {
+            (
+                event.id.as_deref(),
+                event.event_type.as_str(),
+                event.release.status.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (Some("1"), "release.created", "draft"),
+            (Some("2"), "release.published", "published"),
+            (Some("3"), "release.unpublished", "draft"),
+        ]
+    );
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn subscribe_release_events_reports_a_drop_before_any_event() {
+    let (base_url, handle) = spawn_server(move |_request| ResponseSpec {
+        status_line: "HTTP/1.1 200 OK".to_string(),
+        headers: vec![("Content-Type".to_string(), "text/event-stream".to_string())],
+        body: ": keep-alive\nretry: 0\n\n".to_string(),
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("test-key".to_string())).unwrap();
+    let mut events = client.subscribe_release_events().unwrap();
+    match events.next() {
+        Some(Err(Error::Transport { method, url, .. })) => {
+            assert_eq!(method, "GET");
+            assert!(url.ends_with("/v1/releases/events"), "{url}");
+        }
+        other => panic!("unexpected item: {other:?}"),
+    }
+    assert!(events.next().is_none());
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn compress_requests_gzips_large_json_bodies() {
+    let metadata = serde_json::json!({ "notes": "release notes ".repeat(200) });
+    let small = serde_json::json!({ "name": "Acme" });
+    let expected = serde_json::to_vec(&serde_json::json!({ "metadata": metadata })).unwrap();
+    let expected_small = serde_json::to_vec(&small).unwrap();
+    let mut calls = 0;
+    let (base_url, handle) = spawn_server_sequence(2, move |request| {
+        calls += 1;
+        if calls == 1 {
+            assert_eq!(
+                request.headers.get("content-encoding").map(String::as_str),
+                Some("gzip")
+            );
+            assert_eq!(
+                request.headers.get("content-length"),
+                Some(&request.body.len().to_string())
+            );
+            assert!(request.body.len() < expected.len());
+            let mut decoded = Vec::new();
+            flate2::read::GzDecoder::new(&request.body[..])
+                .read_to_end(&mut decoded)
+                .expect("gzip body");
+            assert_eq!(decoded, expected);
+        } else {
+            // Small bodies are not worth compressing.
+            assert_eq!(request.headers.get("content-encoding"), None);
+            assert_eq!(request.body, expected_small);
+        }
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"ok":true}"#.to_string(),
+        }
+    });
+
+    let client = Client::builder(base_url, Auth::AdminKey("admin-key".to_string()))
+        .unwrap()
+        .compress_requests(true)
+        .build()
+        .unwrap();
+    let _: serde_json::Value = client
+        .post_json("/v1/custom", &serde_json::json!({ "metadata": metadata }))
+        .unwrap();
+    let _: serde_json::Value = client.post_json("/v1/custom", &small).unwrap();
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn from_profile_reads_named_profiles() {
+    let path = write_temp_file(
+        br#"# Releasy credentials
+[default]
+base_url = "https://releasy.example.com"
+auth = "api"
+key = "rk_default"
+
+[staging]
+base_url = 'https://staging.releasy.example.com/' # trailing slash is trimmed
+auth = "admin_operator"
+admin_key = "admin-key"
+operator_jwt = "jwt.token"
+"#,
+    );
+
+    let client = Client::from_profile(Some(path.clone()), None).unwrap();
+    assert_eq!(client.base_url(), "https://releasy.example.com");
+    assert_eq!(client.auth_kind(), "api");
+
+    let client = Client::from_profile(Some(path.clone()), Some("staging")).unwrap();
+    assert_eq!(client.base_url(), "https://staging.releasy.example.com");
+    assert_eq!(client.auth_kind(), "admin_operator");
+
+    let error = Client::from_profile(Some(path.clone()), Some("prod")).expect_err("no profile");
+    match &error {
+        Error::Credentials {
+            path: error_path,
+            message,
+        } => {
+            assert_eq!(error_path, &path);
+            assert!(message.contains("profile `prod` not found"), "{message}");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    std::fs::remove_file(&path).expect("remove temp file");
+    let error = Client::from_profile(Some(path), None).expect_err("no file");
+    assert!(matches!(error, Error::Credentials { .. }));
+}
+
+#[test]
+fn from_profile_parses_string_escapes_strictly() {
+    let profile = |value: &str| {
+        let path =
+            write_temp_file(format!("[default]\nbase_url = {value}\nauth = \"none\"\n").as_bytes());
+        let result = Client::from_profile(Some(path.clone()), None)
+            .map(|client| client.base_url().to_string())
+            .map_err(Box::new);
+        std::fs::remove_file(&path).expect("remove temp file");
+        result
+    };
+
+    for (value, expected) in [
+        (
+            r#""https://releasy.example.com/\u0041""#,
+            "https://releasy.example.com/A",
+        ),
+        (
+            r#""https://releasy.example.com/\U0001F600""#,
+            "https://releasy.example.com/\u{1F600}",
+        ),
+        (
+            r#""https://releasy.example.com/a\"b""#,
+            "https://releasy.example.com/a\"b",
+        ),
+        (
+            r#"'https://releasy.example.com/\u41'"#,
+            r"https://releasy.example.com/\u41",
+        ),
+    ] {
+        let base_url = profile(value).unwrap_or_else(|err| panic!("{value}: {err}"));
+        assert_eq!(base_url, expected);
     }
+
+    for value in [
+        r#""https://releasy.example.com/\u41""#,
+        r#""https://releasy.example.com/\u+041""#,
+        r#""https://releasy.example.com/\U0041""#,
+        r#""https://releasy.example.com/\uD800""#,
+        r#""https://releasy.example.com/\x41""#,
+        "\"https://releasy.example.com/\u{1}\"",
+        r#""https://releasy.example.com"#,
+        r#""https://releasy.example.com" trailing"#,
+    ] {
+        let error = profile(value).expect_err(value);
+        assert!(
+            matches!(*error, Error::Credentials { .. }),
+            "{value}: {error:?}"
+        );
+    }
+}
+
+#[test]
+fn check_auth_accepts_active_and_rejects_inactive_keys() {
+    let (base_url, handle) = spawn_server_sequence(2, move |request| {
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/auth/introspect");
+        let key = request.headers.get("x-releasy-api-key").cloned().unwrap();
+        let active = key == "good-key";
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: format!(
+                r#"{{"active":{active},"api_key_id":"{key}","customer_id":"cust-1","key_type":"api","scopes":["releases:read"]}}"#
+            ),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::api_key("good-key")).unwrap();
+    let introspection = client.check_auth().unwrap();
+    assert!(introspection.active);
+    assert_eq!(introspection.api_key_id, "good-key");
+
+    let revoked = client.with_auth(Auth::api_key("revoked-key"));
+    match revoked.check_auth().expect_err("inactive key") {
+        Error::Unauthorized { api_key_id } => assert_eq!(api_key_id, "revoked-key"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn list_queries_clamp_limit_to_max_page_size() {
+    captured_logs("");
+    let (base_url, handle) = spawn_server_sequence(3, move |request| {
+        let (path, params) = parse_query(&request.path);
+        let expected = match path.as_str() {
+            "/v1/releases" => "100",
+            "/v1/admin/users" => "25",
+            other => panic!("unexpected path {other}"),
+        };
+        assert_eq!(params.get("limit").map(String::as_str), Some(expected));
+        let body = if path == "/v1/releases" {
+            r#"{"releases":[],"limit":100,"offset":0}"#
+        } else {
+            r#"{"users":[]}"#
+        };
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url.clone(), Auth::ApiKey("test-key".to_string())).unwrap();
+    let query = ReleaseListQuery {
+        limit: Some(1000),
+        ..Default::default()
+    };
+    client.list_releases(&query).unwrap();
+    assert!(
+        captured_logs("clamping page size")
+            .iter()
+            .any(|line| line == "clamping page size 1000 to 100")
+    );
+
+    let client = Client::builder(base_url, Auth::AdminKey("admin-key".to_string()))
+        .unwrap()
+        .max_page_size(25)
+        .build()
+        .unwrap();
+    let query = UserListQuery {
+        limit: Some(1000),
+        ..Default::default()
+    };
+    client.list_users(&query).unwrap();
+    // Limits within range are sent unchanged.
+    let query = UserListQuery {
+        limit: Some(25),
+        ..Default::default()
+    };
+    client.list_users(&query).unwrap();
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn resolve_download_token_for_reports_purpose_mismatch() {
+    let (base_url, handle) = spawn_server_sequence(2, |request| {
+        let (path, params) = parse_query(&request.path);
+        assert_eq!(path, "/v1/downloads/tok-1");
+        match params.get("purpose").map(String::as_str) {
+            Some("install") => ResponseSpec {
+                status_line: "HTTP/1.1 403 Forbidden".to_string(),
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body:
+                    r#"{"error":{"code":"purpose_mismatch","message":"token minted for update"}}"#
+                        .to_string(),
+            },
+            Some("update") => ResponseSpec {
+                status_line: "HTTP/1.1 302 Found".to_string(),
+                headers: vec![(
+                    "Location".to_string(),
+                    "https://cdn.example.com/artifact.bin".to_string(),
+                )],
+                body: "".to_string(),
+            },
+            other => panic!("unexpected purpose: {other:?}"),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::ApiKey("secret".to_string())).unwrap();
+    let err = client
+        .resolve_download_token_for("tok-1", DownloadPurpose::Install)
+        .unwrap_err();
+    match &err {
+        Error::PurposeMismatch { expected, message } => {
+            assert_eq!(expected, "install");
+            assert_eq!(message, "token minted for update");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    let resolution = client
+        .resolve_download_token_for("tok-1", DownloadPurpose::Update)
+        .unwrap();
+    assert_eq!(resolution.location, "https://cdn.example.com/artifact.bin");
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn list_methods_accept_queries_by_reference_or_value() {
+    let (base_url, handle) = spawn_server_sequence(2, |request| {
+        let (path, params) = parse_query(&request.path);
+        assert_eq!(path, "/v1/releases");
+        assert_eq!(params.get("product").map(String::as_str), Some("demo"));
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"releases":[],"limit":10,"offset":0}"#.to_string(),
+        }
+    });
+    let client = Client::new(base_url, Auth::ApiKey("secret".to_string())).unwrap();
+
+    let query = ReleaseListQuery {
+        product: Some("demo".to_string()),
+        ..Default::default()
+    };
+    assert!(client.list_releases(&query).unwrap().releases.is_empty());
+    let response = client
+        .list_releases(ReleaseListQuery {
+            product: Some("demo".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(response.releases.is_empty());
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn small_transfer_buffers_keep_large_transfers_byte_exact() {
+    let payload: String = (0..200_000u32)
+        .map(|index| char::from(b'a' + (index % 26) as u8))
+        .collect();
+    let uploaded = Arc::new(Mutex::new(Vec::new()));
+    let captured = uploaded.clone();
+    let served = payload.clone();
+    let (base_url, handle) = spawn_server_sequence(4, move |request| match request.path.as_str() {
+        "/upload" => {
+            assert_eq!(request.method, "PUT");
+            *captured.lock().unwrap() = request.body;
+            ResponseSpec {
+                status_line: "HTTP/1.1 200 OK".to_string(),
+                headers: vec![],
+                body: "".to_string(),
+            }
+        }
+        "/v1/downloads/token" => ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"download_url":"/v1/downloads/tok-1","expires_at":1700000000}"#.to_string(),
+        },
+        "/v1/downloads/tok-1" => {
+            let host = request.headers.get("host").expect("host header");
+            ResponseSpec {
+                status_line: "HTTP/1.1 302 Found".to_string(),
+                headers: vec![(
+                    "Location".to_string(),
+                    format!("http://{}/files/artifact.bin", host),
+                )],
+                body: "".to_string(),
+            }
+        }
+        "/files/artifact.bin" => ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![],
+            body: served.clone(),
+        },
+        other => panic!("unexpected path: {other}"),
+    });
+
+    let client = Client::builder(base_url.clone(), Auth::ApiKey("secret".to_string()))
+        .unwrap()
+        .transfer_buffer_size(7)
+        .build()
+        .unwrap();
+    let path = write_temp_file(payload.as_bytes());
+    client
+        .upload_presigned_artifact(&format!("{}/upload", base_url), &path)
+        .unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_eq!(uploaded.lock().unwrap().as_slice(), payload.as_bytes());
+
+    let bytes = client
+        .clone()
+        .download_artifact_bytes(&DownloadTokenRequest {
+            artifact_id: "art-1".into(),
+            expires_in_seconds: None,
+            purpose: None,
+        })
+        .unwrap();
+    assert_eq!(bytes, payload.as_bytes());
+
+    handle.join().expect("server join");
+}
+
+#[test]
+fn upload_payload_too_large_is_detected() {
+    let body = "<Error><Code>EntityTooLarge</Code></Error>";
+    let (base_url, handle) = spawn_server(move |request| {
+        assert_eq!(request.method, "PUT");
+        ResponseSpec {
+            status_line: "HTTP/1.1 413 Payload Too Large".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/xml".to_string())],
+            body: body.to_string(),
+        }
+    });
+    let path = write_temp_file(b"releasy-upload-bytes");
+    let client = Client::new(base_url.clone(), Auth::None).unwrap();
+    let err = client
+        .upload_presigned_artifact(&format!("{}/upload", base_url), &path)
+        .unwrap_err();
+    assert!(err.is_payload_too_large(), "unexpected error: {err:?}");
+    assert_eq!(err.body(), Some(body));
+    handle.join().expect("server join");
+
+    let limited = Client::builder("http://localhost", Auth::None)
+        .unwrap()
+        .max_upload_bytes(8)
+        .build()
+        .unwrap();
+    let err = limited
+        .upload_presigned_artifact("http://localhost/upload", &path)
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::PayloadTooLarge { size: 20, limit: 8 }),
+        "unexpected error: {err:?}"
+    );
+    assert!(err.is_payload_too_large());
+    assert!(!Error::Cancelled.is_payload_too_large());
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn on_behalf_of_adds_header_to_scoped_client_only() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorder = seen.clone();
+    let (base_url, handle) = spawn_server_sequence(2, move |request| {
+        assert_eq!(request.path, "/v1/admin/customers/cust-1");
+        assert_eq!(
+            request.headers.get("x-releasy-admin-key"),
+            Some(&"admin-key".to_string())
+        );
+        recorder
+            .lock()
+            .unwrap()
+            .push(request.headers.get("x-releasy-on-behalf-of").cloned());
+        ResponseSpec {
+            status_line: "HTTP/1.1 200 OK".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"id":"cust-1","name":"Acme","created_at":1700000000}"#.to_string(),
+        }
+    });
+
+    let client = Client::new(base_url, Auth::AdminKey("admin-key".to_string())).unwrap();
+    let scoped = client.on_behalf_of("cust-1");
+    assert_eq!(scoped.acting_on_behalf_of().unwrap(), "cust-1");
+    assert!(client.acting_on_behalf_of().is_none());
+
+    scoped.get_customer("cust-1").unwrap();
+    client.get_customer("cust-1").unwrap();
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![Some("cust-1".to_string()), None]
+    );
+
+    handle.join().expect("server join");
 }
@@ -0,0 +1,661 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use releasy_client::{
+    AdminCreateKeyRequest, AdminCustomerResponse, ApiKeyIntrospection, ArtifactId,
+    ArtifactPresignRequest, ArtifactRegisterRequest, ArtifactSummary, Auth, Checksum,
+    ChecksumAlgorithm, ChecksumFormat, Clock, CustomerId, DownloadTokenResponse,
+    EntitlementCreateRequest, Error, Jitter, JitterRng, ManualClock, Platform, ReleaseListQuery,
+    ReleaseListResponse, ReleaseResponse, RetryPolicy, Scope, SparseRelease, UserResponse,
+};
+
+#[test]
+fn platform_serializes_known_variants_as_strings() {
+    let request = ArtifactPresignRequest {
+        filename: "demo.tar.gz".to_string(),
+        platform: Platform::MacOs,
+    };
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["platform"], "macos");
+
+    assert_eq!(serde_json::to_value(Platform::Linux).unwrap(), "linux");
+    assert_eq!(serde_json::to_value(Platform::Windows).unwrap(), "windows");
+}
+
+#[test]
+fn platform_falls_back_to_other_for_unknown_values() {
+    let body =
+        r#"{"id":"art-1","object_key":"k","platform":"linux-arm64","checksum":"abc","size":3}"#;
+    let summary: ArtifactSummary = serde_json::from_str(body).unwrap();
+    assert_eq!(summary.platform, Platform::Other("linux-arm64".to_string()));
+
+    let json = serde_json::to_value(&summary).unwrap();
+    assert_eq!(json["platform"], "linux-arm64");
+}
+
+#[test]
+fn platform_parses_known_values() {
+    assert_eq!("windows".parse::<Platform>().unwrap(), Platform::Windows);
+    assert_eq!(Platform::from("linux".to_string()), Platform::Linux);
+    assert_eq!(Platform::MacOs.to_string(), "macos");
+}
+
+#[test]
+fn platform_current_matches_host_os() {
+    assert_eq!(Platform::current().as_str(), std::env::consts::OS);
+}
+
+#[test]
+fn key_request_builder_serializes_known_scopes() {
+    let request = AdminCreateKeyRequest::builder("cust-1")
+        .scope(Scope::ReleasesRead)
+        .scope(Scope::ReleasesWrite)
+        .scope(Scope::DownloadsToken)
+        .name("ci")
+        .build();
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "customer_id": "cust-1",
+            "name": "ci",
+            "scopes": ["releases:read", "releases:write", "downloads:token"],
+        })
+    );
+}
+
+#[test]
+fn scope_falls_back_to_other_for_unknown_values() {
+    let request = AdminCreateKeyRequest::builder("cust-1")
+        .scope(Scope::Other("audit:read".to_string()))
+        .build();
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["scopes"], serde_json::json!(["audit:read"]));
+
+    assert_eq!(
+        "releases:read".parse::<Scope>().unwrap(),
+        Scope::ReleasesRead
+    );
+    assert_eq!(
+        serde_json::from_str::<Scope>(r#""audit:read""#).unwrap(),
+        Scope::Other("audit:read".to_string())
+    );
+    assert_eq!(Scope::DownloadsToken.to_string(), "downloads:token");
+}
+
+#[test]
+fn key_request_builder_without_scopes_omits_them() {
+    let request = AdminCreateKeyRequest::builder("cust-1").build();
+    assert_eq!(request.scopes, None);
+    let json = serde_json::to_value(&request).unwrap();
+    assert!(json.get("scopes").is_none());
+}
+
+#[test]
+fn resource_ids_serialize_as_plain_strings() {
+    let id = CustomerId::from("cust-1".to_string());
+    assert_eq!(serde_json::to_value(&id).unwrap(), "cust-1");
+    assert_eq!(
+        serde_json::from_str::<CustomerId>(r#""cust-1""#).unwrap(),
+        id
+    );
+    assert_eq!(id.to_string(), "cust-1");
+    assert_eq!(id, "cust-1");
+
+    let body = r#"{"id":"art-1","object_key":"k","platform":"linux","checksum":"abc","size":3}"#;
+    let summary: ArtifactSummary = serde_json::from_str(body).unwrap();
+    assert_eq!(summary.id, ArtifactId::new("art-1"));
+    assert_eq!(serde_json::to_value(&summary).unwrap()["id"], "art-1");
+}
+
+const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+const ABC_MD5: &str = "900150983cd24fb0d6963f7d28e17f72";
+
+#[test]
+fn checksum_formats_prefixed_or_bare() {
+    let checksum = Checksum::of_bytes(ChecksumAlgorithm::Sha256, b"abc");
+    assert_eq!(checksum.to_string(), format!("sha256:{ABC_SHA256}"));
+    assert_eq!(checksum.format(ChecksumFormat::Bare), ABC_SHA256);
+
+    let checksum = Checksum::of_bytes(ChecksumAlgorithm::Md5, b"abc");
+    assert_eq!(
+        checksum.format(ChecksumFormat::Prefixed),
+        format!("md5:{ABC_MD5}")
+    );
+    assert_eq!(checksum.format(ChecksumFormat::Bare), ABC_MD5);
+
+    let request = ArtifactRegisterRequest {
+        artifact_id: "art-1".into(),
+        object_key: "releases/rel-1/a.bin".to_string(),
+        checksum: String::new(),
+        size: 3,
+        platform: Platform::Linux,
+    }
+    .with_checksum(&checksum, ChecksumFormat::Bare);
+    assert_eq!(request.checksum, ABC_MD5);
+}
+
+#[test]
+fn checksum_is_computed_from_files() {
+    let path = std::env::temp_dir().join(format!("releasy-checksum-{}", std::process::id()));
+    std::fs::write(&path, b"abc").unwrap();
+
+    let sha256 = Checksum::of_file(ChecksumAlgorithm::Sha256, &path).unwrap();
+    let md5 = Checksum::of_file(ChecksumAlgorithm::Md5, &path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(
+        sha256,
+        Checksum::of_bytes(ChecksumAlgorithm::Sha256, b"abc")
+    );
+    assert_eq!(sha256.value, ABC_SHA256);
+    assert_eq!(md5.algorithm, ChecksumAlgorithm::Md5);
+    assert_eq!(md5.value, ABC_MD5);
+}
+
+#[test]
+fn checksums_parse_with_their_algorithm() {
+    let md5 = Checksum::of_bytes(ChecksumAlgorithm::Md5, b"abc");
+    let sha256 = Checksum::of_bytes(ChecksumAlgorithm::Sha256, b"abc");
+    for text in [
+        format!("md5:{ABC_MD5}"),
+        format!("MD5:{}", ABC_MD5.to_uppercase()),
+        ABC_MD5.to_string(),
+    ] {
+        assert_eq!(Checksum::parse(&text), Some(md5.clone()), "{text}");
+    }
+    assert_eq!(Checksum::parse(&format!(" {ABC_SHA256} ")), Some(sha256));
+    for text in [
+        "",
+        "abc",
+        "crc32:0000",
+        "md5:00",
+        &format!("sha256:{ABC_MD5}"),
+    ] {
+        assert_eq!(Checksum::parse(text), None, "{text}");
+    }
+
+    let artifact = |checksum: String| ArtifactSummary {
+        id: "art-1".into(),
+        object_key: "releases/a.bin".to_string(),
+        platform: Platform::Linux,
+        checksum,
+        size: 3,
+    };
+    let release = |checksum: String| ReleaseResponse {
+        id: "rel-1".into(),
+        product: "demo-app".to_string(),
+        version: "1.0.0".to_string(),
+        status: "published".to_string(),
+        created_at: 1_700_000_000,
+        published_at: None,
+        artifacts: Some(vec![artifact(checksum)]),
+        updated_at: None,
+    };
+    let prefixed = release(format!("md5:{ABC_MD5}"));
+    assert!(
+        prefixed
+            .artifact_diff(&release(ABC_MD5.to_string()))
+            .is_empty()
+    );
+    assert_eq!(
+        prefixed
+            .artifact_diff(&release(format!("sha256:{ABC_SHA256}")))
+            .changed
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn pagination_fields_refuse_negative_values() {
+    let query: Result<ReleaseListQuery, _> = serde_json::from_str(r#"{"offset":-1}"#);
+    assert!(query.is_err());
+
+    let response: Result<ReleaseListResponse, _> =
+        serde_json::from_str(r#"{"releases":[],"limit":-5,"offset":0}"#);
+    assert!(response.is_err());
+}
+
+#[test]
+fn perpetual_entitlement_has_no_expiry() {
+    let request = EntitlementCreateRequest::perpetual("demo", 1_700_000_000);
+    assert_eq!(request.product, "demo");
+    assert_eq!(request.starts_at, 1_700_000_000);
+    assert_eq!(request.ends_at, None);
+    assert!(request.validate().is_ok());
+    let json = serde_json::to_value(&request).unwrap();
+    assert!(json.get("ends_at").is_none());
+}
+
+#[test]
+fn entitlement_window_must_end_after_start() {
+    let mut request = EntitlementCreateRequest::perpetual("demo", 1_700_000_000);
+    request.ends_at = Some(1_700_000_000);
+    assert!(matches!(request.validate(), Err(Error::Validation { .. })));
+    request.ends_at = Some(1_600_000_000);
+    assert!(matches!(request.validate(), Err(Error::Validation { .. })));
+    request.ends_at = Some(1_700_000_001);
+    assert!(request.validate().is_ok());
+}
+
+#[test]
+fn operator_jwt_claims_are_decoded_without_verification() {
+    let auth = Auth::OperatorJwt(
+        "eyJhbGciOiJub25lIn0.eyJzdWIiOiJvcC0xIiwiZXhwIjoxOTAwMDAwMDAwLCJhdWQiOiJyZWxlYXN5LWFwaSIsInNjb3BlIjoicmVsZWFzZXM6d3JpdGUgYWRtaW46cmVhZCJ9.sig"
+            .to_string(),
+    );
+    let claims = auth.decode_jwt_claims().expect("claims");
+    assert_eq!(claims.exp, Some(1_900_000_000));
+    assert_eq!(claims.aud, vec!["releasy-api".to_string()]);
+    assert!(claims.has_scope("releases:write"));
+    assert!(!claims.has_scope("releases"));
+
+    let auth = Auth::OperatorJwt(
+        "eyJhbGciOiJub25lIn0.eyJhdWQiOlsiYSIsImIiXSwic2NvcGVzIjpbInJlbGVhc2VzOnJlYWQiXX0.sig"
+            .to_string(),
+    );
+    let claims = auth.decode_jwt_claims().expect("claims");
+    assert_eq!(claims.exp, None);
+    assert_eq!(claims.aud, vec!["a".to_string(), "b".to_string()]);
+    assert!(claims.has_scope("releases:read"));
+}
+
+#[test]
+fn malformed_or_non_jwt_auth_has_no_claims() {
+    for token in ["not-a-jwt", "a.b", "a.!!!.c", "a.bm90LWpzb24.c", "a.b.c.d"] {
+        let auth = Auth::OperatorJwt(token.to_string());
+        assert_eq!(auth.decode_jwt_claims(), None, "{token}");
+    }
+    assert_eq!(Auth::ApiKey("key".to_string()).decode_jwt_claims(), None);
+}
+
+#[test]
+fn release_response_requires_core_fields() {
+    let truncated = r#"{"id":"rel-1","version":"1.0.0"}"#;
+    assert!(serde_json::from_str::<ReleaseResponse>(truncated).is_err());
+
+    let sparse: SparseRelease = serde_json::from_str(truncated).unwrap();
+    assert_eq!(sparse.version.as_deref(), Some("1.0.0"));
+    assert_eq!(sparse.product, None);
+    assert_eq!(sparse.created_at, None);
+}
+
+#[test]
+fn epoch_timestamps_convert_to_system_time() {
+    let body = r#"{"id":"rel-1","product":"demo","version":"1.0.0","status":"draft","created_at":1700000000}"#;
+    let release: ReleaseResponse = serde_json::from_str(body).unwrap();
+    let created = release.created_at_system_time();
+    assert_eq!(
+        created.duration_since(UNIX_EPOCH).unwrap(),
+        Duration::from_secs(1_700_000_000)
+    );
+    assert_eq!(release.published_at_system_time(), None);
+
+    let token = DownloadTokenResponse {
+        download_url: "/v1/downloads/tok".to_string(),
+        expires_at: 1_700_000_600,
+    };
+    let expires = token.expires_at_system_time();
+    assert_eq!(
+        expires.duration_since(created).unwrap(),
+        Duration::from_secs(600)
+    );
+}
+
+#[test]
+fn extreme_epoch_timestamps_saturate() {
+    let token = |expires_at| DownloadTokenResponse {
+        download_url: "/v1/downloads/tok".to_string(),
+        expires_at,
+    };
+    let latest = token(i64::MAX).expires_at_system_time();
+    let earliest = token(i64::MIN).expires_at_system_time();
+    assert!(latest > UNIX_EPOCH + Duration::from_secs(1 << 40));
+    assert!(earliest < UNIX_EPOCH);
+    assert!(latest.checked_add(Duration::from_secs(1)).is_none());
+    assert!(!token(i64::MAX).is_expired_at(SystemTime::now()));
+    assert!(token(i64::MIN).is_expired_at(SystemTime::now()));
+}
+
+#[test]
+fn response_models_display_as_one_liners() {
+    let release = ReleaseResponse {
+        id: "rel-1".into(),
+        product: "demo-app".to_string(),
+        version: "1.2.3".to_string(),
+        status: "published".to_string(),
+        created_at: 1_700_000_000,
+        published_at: None,
+        artifacts: None,
+        updated_at: None,
+    };
+    assert_eq!(release.to_string(), "demo-app@1.2.3 [published]");
+
+    let mut customer = AdminCustomerResponse {
+        id: "cust-1".into(),
+        name: "Acme".to_string(),
+        created_at: 1_700_000_000,
+        plan: Some("pro".to_string()),
+        suspended_at: None,
+        plan_changed_at: None,
+        extra: Default::default(),
+    };
+    assert_eq!(customer.to_string(), "Acme (cust-1) [pro]");
+    customer.plan = None;
+    customer.suspended_at = Some(1_700_000_100);
+    assert_eq!(customer.to_string(), "Acme (cust-1) [suspended]");
+
+    let mut user = UserResponse {
+        id: "user-1".into(),
+        keycloak_user_id: "kc-1".to_string(),
+        customer_id: "cust-1".into(),
+        email: "alice@example.com".to_string(),
+        status: "active".to_string(),
+        groups: vec![],
+        created_at: 1_700_000_000,
+        updated_at: 1_700_000_000,
+        disabled_at: None,
+        display_name: Some("Alice".to_string()),
+        last_synced_at: None,
+        metadata: None,
+    };
+    assert_eq!(user.to_string(), "Alice <alice@example.com> [active]");
+    user.display_name = None;
+    assert_eq!(user.to_string(), "alice@example.com [active]");
+}
+
+#[test]
+fn auth_constructors_pick_the_matching_variant() {
+    assert_eq!(Auth::api_key("k"), Auth::ApiKey("k".to_string()));
+    assert_eq!(Auth::admin_key("k"), Auth::AdminKey("k".to_string()));
+    assert_eq!(
+        Auth::operator_jwt(String::from("t")),
+        Auth::OperatorJwt("t".to_string())
+    );
+}
+
+#[test]
+fn auth_from_env_selects_the_configured_credentials() {
+    let vars = [
+        Auth::ADMIN_KEY_ENV,
+        Auth::API_KEY_ENV,
+        Auth::OPERATOR_JWT_ENV,
+    ];
+    // SAFETY: this is the only test in the binary touching the environment.
+    let set = |values: [Option<&str>; 3]| {
+        for (name, value) in vars.iter().zip(values) {
+            match value {
+                Some(value) => unsafe { std::env::set_var(name, value) },
+                None => unsafe { std::env::remove_var(name) },
+            }
+        }
+    };
+
+    set([None, None, None]);
+    assert_eq!(Auth::from_env().unwrap(), Auth::None);
+
+    set([None, Some(" api-key \n"), Some("")]);
+    assert_eq!(Auth::from_env().unwrap(), Auth::api_key("api-key"));
+
+    set([Some("admin-key"), None, None]);
+    assert_eq!(Auth::from_env().unwrap(), Auth::admin_key("admin-key"));
+
+    set([None, None, Some("jwt")]);
+    assert_eq!(Auth::from_env().unwrap(), Auth::operator_jwt("jwt"));
+
+    set([Some("admin-key"), Some("api-key"), None]);
+    match Auth::from_env().expect_err("expected conflict") {
+        Error::ConfigConflict { message } => {
+            assert!(message.contains("RELEASY_ADMIN_KEY"));
+            assert!(message.contains("RELEASY_API_KEY"));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    set([None, None, None]);
+}
+
+#[test]
+fn expiry_checks_use_the_given_time() {
+    let clock = ManualClock::new(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+    let token = DownloadTokenResponse {
+        download_url: "/v1/downloads/tok".to_string(),
+        expires_at: 1_700_000_060,
+    };
+    assert!(!token.is_expired_at(clock.now()));
+    clock.advance(Duration::from_secs(60));
+    assert!(token.is_expired_at(clock.now()));
+
+    let introspection = ApiKeyIntrospection {
+        active: true,
+        api_key_id: "key-1".to_string(),
+        customer_id: "cust-1".into(),
+        key_type: "api".to_string(),
+        scopes: vec![],
+        expires_at: Some(1_700_003_600),
+    };
+    clock.set(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+    assert!(!introspection.expires_within(Duration::from_secs(3599), clock.now()));
+    assert!(introspection.expires_within(Duration::from_secs(3600), clock.now()));
+    let perpetual = ApiKeyIntrospection {
+        expires_at: None,
+        ..introspection
+    };
+    assert!(!perpetual.expires_within(Duration::MAX / 2, clock.now()));
+}
+
+fn jitter_delays(jitter: Jitter) -> Vec<(Duration, Duration)> {
+    let policy = RetryPolicy::new(6)
+        .initial_backoff(Duration::from_millis(100))
+        .max_backoff(Duration::from_secs(2))
+        .jitter(jitter);
+    let mut rng = JitterRng::seeded(42);
+    let mut previous = policy.initial_backoff;
+    (1..=6)
+        .map(|retry| {
+            let delay = policy.delay(retry, previous, &mut rng);
+            let prior = previous;
+            previous = delay;
+            (prior, delay)
+        })
+        .collect()
+}
+
+fn exponential(retry: usize) -> Duration {
+    (Duration::from_millis(100) * 2u32.pow(retry as u32)).min(Duration::from_secs(2))
+}
+
+#[test]
+fn retry_policy_defaults_to_full_jitter() {
+    assert_eq!(RetryPolicy::default().jitter, Jitter::Full);
+    let policy: RetryPolicy = serde_json::from_str(
+        r#"{"max_retries":1,"initial_backoff":{"secs":0,"nanos":0},"max_backoff":{"secs":1,"nanos":0}}"#,
+    )
+    .unwrap();
+    assert_eq!(policy.jitter, Jitter::Full);
+}
+
+#[test]
+fn no_jitter_uses_exponential_backoff() {
+    for (retry, (_, delay)) in jitter_delays(Jitter::None).into_iter().enumerate() {
+        assert_eq!(delay, exponential(retry));
+    }
+}
+
+#[test]
+fn full_jitter_stays_below_backoff() {
+    let delays = jitter_delays(Jitter::Full);
+    for (retry, (_, delay)) in delays.iter().enumerate() {
+        assert!(*delay <= exponential(retry), "retry {retry}: {delay:?}");
+    }
+    // Seeded delays are randomized, not the plain exponential sequence.
+    assert!(
+        delays
+            .iter()
+            .enumerate()
+            .any(|(retry, (_, delay))| *delay != exponential(retry))
+    );
+    assert_eq!(delays, jitter_delays(Jitter::Full));
+}
+
+#[test]
+fn equal_jitter_keeps_half_the_backoff() {
+    for (retry, (_, delay)) in jitter_delays(Jitter::Equal).into_iter().enumerate() {
+        let base = exponential(retry);
+        assert!(
+            delay >= base / 2 && delay <= base,
+            "retry {retry}: {delay:?}"
+        );
+    }
+}
+
+#[test]
+fn decorrelated_jitter_grows_from_previous_delay() {
+    for (retry, (previous, delay)) in jitter_delays(Jitter::Decorrelated).into_iter().enumerate() {
+        let upper = (previous * 3).min(Duration::from_secs(2));
+        assert!(
+            delay >= Duration::from_millis(100) && delay <= upper,
+            "retry {retry}: {delay:?}"
+        );
+    }
+}
+
+fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+#[test]
+fn error_is_send_sync() {
+    assert_send_sync::<Error>();
+
+    fn app() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Err(Error::Cancelled)?
+    }
+    assert_eq!(app().unwrap_err().to_string(), "transfer cancelled");
+}
+
+#[test]
+fn artifact_diff_reports_added_removed_and_changed_artifacts() {
+    fn artifact(id: &str, platform: Platform, checksum: &str) -> ArtifactSummary {
+        ArtifactSummary {
+            id: id.into(),
+            object_key: format!("releases/{}", id),
+            platform,
+            checksum: checksum.to_string(),
+            size: 3,
+        }
+    }
+    fn release(version: &str, artifacts: Vec<ArtifactSummary>) -> ReleaseResponse {
+        ReleaseResponse {
+            id: format!("rel-{}", version).into(),
+            product: "demo-app".to_string(),
+            version: version.to_string(),
+            status: "published".to_string(),
+            created_at: 1_700_000_000,
+            published_at: None,
+            artifacts: Some(artifacts),
+            updated_at: None,
+        }
+    }
+
+    let old = release(
+        "1.0.0",
+        vec![
+            artifact("art-1", Platform::Linux, "aaa"),
+            artifact("art-2", Platform::MacOs, "bbb"),
+            artifact("art-3", Platform::Windows, "ccc"),
+        ],
+    );
+    let new = release(
+        "1.1.0",
+        vec![
+            artifact("art-4", Platform::Linux, "sha256:AAA"),
+            artifact("art-5", Platform::MacOs, "bbb2"),
+            artifact("art-6", Platform::Other("linux-arm64".to_string()), "ddd"),
+        ],
+    );
+
+    let diff = old.artifact_diff(&new);
+    assert_eq!(diff.only_in_self.len(), 1);
+    assert_eq!(diff.only_in_self[0].platform, Platform::Windows);
+    assert_eq!(diff.only_in_other.len(), 1);
+    assert_eq!(diff.only_in_other[0].id, "art-6");
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(diff.changed[0].0.checksum, "bbb");
+    assert_eq!(diff.changed[0].1.checksum, "bbb2");
+    assert!(!diff.is_empty());
+
+    let reversed = new.artifact_diff(&old);
+    assert_eq!(reversed.only_in_self, diff.only_in_other);
+    assert_eq!(reversed.only_in_other, diff.only_in_self);
+    assert!(old.artifact_diff(&old).is_empty());
+
+    let without = ReleaseResponse {
+        artifacts: None,
+        ..old.clone()
+    };
+    assert_eq!(without.artifact_diff(&old).only_in_other.len(), 3);
+}
+
+#[test]
+fn timestamps_accept_epoch_seconds_and_rfc3339_strings() {
+    let numeric: ReleaseResponse = serde_json::from_str(
+        r#"{"id":"rel-1","product":"demo","version":"1.0.0","status":"published",
+            "created_at":1700000000,"published_at":1700000060}"#,
+    )
+    .unwrap();
+    let textual: ReleaseResponse = serde_json::from_str(
+        r#"{"id":"rel-1","product":"demo","version":"1.0.0","status":"published",
+            "created_at":"2023-11-14T22:13:20Z","published_at":"2023-11-14T23:14:20.5+01:00"}"#,
+    )
+    .unwrap();
+    assert_eq!(textual, numeric);
+    assert_eq!(textual.created_at, 1_700_000_000);
+    assert_eq!(textual.published_at, Some(1_700_000_060));
+    assert_eq!(textual.updated_at, None);
+
+    let token: DownloadTokenResponse = serde_json::from_str(
+        r#"{"download_url":"https://cdn.example.com/a","expires_at":"1970-01-01T00:00:00-00:30"}"#,
+    )
+    .unwrap();
+    assert_eq!(token.expires_at, 1800);
+
+    let explicit_null: SparseRelease =
+        serde_json::from_str(r#"{"created_at":0,"published_at":null}"#).unwrap();
+    assert_eq!(explicit_null.published_at, None);
+
+    for bad in [
+        r#""2023-13-01T00:00:00Z""#,
+        r#""2023-11-14 22:13:20""#,
+        "true",
+    ] {
+        let body = format!(r#"{{"created_at":{}}}"#, bad);
+        assert!(
+            serde_json::from_str::<SparseRelease>(&body).is_err(),
+            "{}",
+            bad
+        );
+    }
+}
+
+#[test]
+fn download_token_expiry_from_unix_seconds() {
+    let token = DownloadTokenResponse {
+        download_url: "/v1/downloads/tok".to_string(),
+        expires_at: 1_700_000_060,
+    };
+
+    assert!(!token.is_expired(1_700_000_000));
+    assert_eq!(
+        token.time_to_expiry(1_700_000_000),
+        Some(Duration::from_secs(60))
+    );
+    assert_eq!(
+        token.time_to_expiry(1_700_000_059),
+        Some(Duration::from_secs(1))
+    );
+
+    assert!(token.is_expired(1_700_000_060));
+    assert_eq!(token.time_to_expiry(1_700_000_060), None);
+
+    assert!(token.is_expired(1_700_000_061));
+    assert_eq!(token.time_to_expiry(1_700_000_061), None);
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "test-utils")]
+
+use releasy_client::test_utils::{MockReleasy, MockResponse};
+use releasy_client::{Auth, Client, ReleaseCreateRequest, ReleaseListQuery, RetryPolicy};
+use ureq::http::Method;
+
+fn release_json(id: &str, version: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "product": "demo-app",
+        "version": version,
+        "status": "draft",
+        "created_at": 1_700_000_000
+    })
+}
+
+#[test]
+fn mock_serves_registered_responses_and_records_requests() {
+    let mock = MockReleasy::start().expect("start mock");
+    mock.respond(
+        Method::POST,
+        "/v1/releases",
+        MockResponse::json(201, &release_json("rel-1", "1.0.0")),
+    );
+    mock.respond(
+        Method::GET,
+        "/v1/releases",
+        MockResponse::json(
+            200,
+            &serde_json::json!({ "releases": [release_json("rel-1", "1.0.0")], "limit": 10, "offset": 0 }),
+        ),
+    );
+    let client = Client::new(mock.base_url(), Auth::ApiKey("secret".to_string())).unwrap();
+
+    let created = client
+        .create_release(&ReleaseCreateRequest {
+            product: "demo-app".to_string(),
+            version: "1.0.0".to_string(),
+        })
+        .unwrap();
+    assert_eq!(created.id, "rel-1");
+    let listed = client
+        .list_releases(&ReleaseListQuery {
+            product: Some("demo-app".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(listed.releases.len(), 1);
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, Method::POST);
+    assert_eq!(requests[0].header("X-Releasy-Api-Key"), Some("secret"));
+    let body: serde_json::Value = requests[0].json().unwrap();
+    assert_eq!(body["version"], "1.0.0");
+    let listed_with = mock.requests_to(Method::GET, "/v1/releases");
+    assert!(
+        listed_with[0]
+            .query_pairs()
+            .contains(&("product", "demo-app"))
+    );
+}
+
+#[test]
+fn mock_queues_responses_and_reports_unregistered_routes() {
+    let mock = MockReleasy::start().expect("start mock");
+    mock.respond(
+        Method::GET,
+        "/health",
+        MockResponse::error(503, "unavailable", "warming up"),
+    );
+    mock.respond(
+        Method::GET,
+        "/health",
+        MockResponse::json(200, &serde_json::json!({ "status": "ok" })),
+    );
+    let client = Client::builder(mock.base_url(), Auth::None)
+        .unwrap()
+        .retry_policy(RetryPolicy::new(0))
+        .build()
+        .unwrap();
+
+    assert!(client.health_check().is_err());
+    assert_eq!(client.health_check().unwrap().status, "ok");
+    assert_eq!(client.health_check().unwrap().status, "ok");
+
+    let err = client.ready_check().unwrap_err();
+    assert_eq!(err.status(), Some(404));
+    assert_eq!(err.api_error().unwrap().error.code, "not_found");
+    assert_eq!(mock.requests().len(), 4);
+}